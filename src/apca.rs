@@ -0,0 +1,250 @@
+// APCA-W3 0.0.98G-4g constants
+const MAIN_TRC: f64 = 2.4;
+const R_COEFFICIENT: f64 = 0.2126729;
+const G_COEFFICIENT: f64 = 0.7151522;
+const B_COEFFICIENT: f64 = 0.0721750;
+
+const NORM_BG: f64 = 0.56;
+const NORM_TEXT: f64 = 0.57;
+const REV_TEXT: f64 = 0.62;
+const REV_BG: f64 = 0.65;
+
+const BLACK_THRESHOLD: f64 = 0.022;
+const BLACK_CLAMP: f64 = 1.414;
+const SCALE: f64 = 1.14;
+const LOW_OFFSET: f64 = 0.027;
+const DELTA_Y_MIN: f64 = 0.0005;
+const LOW_CLIP: f64 = 0.1;
+
+// The font lookup table of apca-w3 0.1.9,
+// used with the 0.0.98G-4g contrast above.
+// Each row has the minimum font size in px
+// for the weights 100 to 900 at an Lc value,
+// 777 is for non-text only and 999 is not allowed
+static FONT_SIZES: [(f64, [f64; 9]); 25] =
+[
+    (0.0, [999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0]),
+    (10.0, [999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0, 999.0]),
+    (15.0, [777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0]),
+    (20.0, [777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0, 777.0]),
+    (25.0, [777.0, 777.0, 777.0, 120.0, 120.0, 108.0, 96.0, 96.0, 96.0]),
+    (30.0, [777.0, 777.0, 120.0, 108.0, 108.0, 96.0, 72.0, 72.0, 72.0]),
+    (35.0, [777.0, 120.0, 108.0, 96.0, 72.0, 60.0, 48.0, 48.0, 48.0]),
+    (40.0, [120.0, 108.0, 96.0, 60.0, 48.0, 42.0, 32.0, 32.0, 32.0]),
+    (45.0, [108.0, 96.0, 72.0, 42.0, 32.0, 28.0, 24.0, 24.0, 24.0]),
+    (50.0, [96.0, 72.0, 60.0, 32.0, 28.0, 24.0, 21.0, 21.0, 21.0]),
+    (55.0, [80.0, 60.0, 48.0, 28.0, 24.0, 21.0, 18.0, 18.0, 18.0]),
+    (60.0, [72.0, 48.0, 42.0, 24.0, 21.0, 18.0, 16.0, 16.0, 18.0]),
+    (65.0, [68.0, 46.0, 32.0, 21.75, 19.0, 17.0, 15.0, 16.0, 18.0]),
+    (70.0, [64.0, 44.0, 28.0, 19.5, 18.0, 16.0, 14.5, 16.0, 18.0]),
+    (75.0, [60.0, 42.0, 24.0, 18.0, 16.0, 15.0, 14.0, 16.0, 18.0]),
+    (80.0, [56.0, 38.25, 23.0, 17.25, 15.81, 14.81, 14.0, 16.0, 18.0]),
+    (85.0, [52.0, 34.5, 22.0, 16.5, 15.625, 14.625, 14.0, 16.0, 18.0]),
+    (90.0, [48.0, 32.0, 21.0, 16.0, 15.5, 14.5, 14.0, 16.0, 18.0]),
+    (95.0, [45.0, 28.0, 19.5, 15.5, 15.0, 14.0, 13.5, 16.0, 18.0]),
+    (100.0, [42.0, 26.5, 18.5, 15.0, 14.5, 13.5, 13.0, 16.0, 18.0]),
+    (105.0, [39.0, 25.0, 18.0, 14.0, 14.0, 13.0, 12.0, 16.0, 18.0]),
+    (110.0, [36.0, 24.0, 18.0, 14.0, 13.0, 12.0, 11.0, 16.0, 18.0]),
+    (115.0, [34.5, 22.5, 17.25, 12.5, 11.875, 11.25, 10.625, 14.5, 16.5]),
+    (120.0, [33.0, 21.0, 16.5, 11.0, 10.75, 10.5, 10.25, 13.0, 15.0]),
+    (125.0, [32.0, 20.0, 16.0, 10.0, 10.0, 10.0, 10.0, 12.0, 14.0])
+];
+
+// Font sizes at or above this are not for text
+const NON_TEXT: f64 = 777.0;
+
+/// The polarity of an APCA contrast.
+///
+/// DarkOnLight is dark text on a light
+/// background, which gives a positive Lc,
+/// and LightOnDark gives a negative Lc.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Polarity
+{
+    DarkOnLight,
+    LightOnDark
+}
+
+impl Polarity
+{
+    /// Gets the polarity of an Lc value,
+    /// None if there is no contrast.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{apca_contrast, Polarity};
+    /// let lc = apca_contrast((255, 255, 255), (0, 0, 0));
+    /// assert_eq!(Polarity::from_lc(lc), Some(Polarity::LightOnDark));
+    /// ```
+    pub fn from_lc(lc: f64) -> Option<Polarity>
+    {
+        if lc > 0.0 {Some(Polarity::DarkOnLight)}
+        else if lc < 0.0 {Some(Polarity::LightOnDark)}
+        else {None}
+    }
+}
+
+/// Gets the APCA lightness contrast (Lc)
+/// of a text color on a background color.
+///
+/// This is the contrast method of the WCAG 3 draft,
+/// implemented from the APCA-W3 0.0.98G-4g constants.
+///
+/// Dark text on a light background gives a
+/// positive value up to about 106, and light text
+/// on a dark background a negative value down to
+/// about -108. Low contrasts are clipped to 0.
+///
+/// Unlike the WCAG 2 ratio, the order matters.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::apca_contrast;
+/// let lc = apca_contrast((136, 136, 136), (255, 255, 255));
+/// assert!((lc - 63.056).abs() < 0.001);
+/// ```
+#[allow(clippy::suspicious_else_formatting)]
+pub fn apca_contrast(text: (u8, u8, u8), background: (u8, u8, u8)) -> f64
+{
+    let (yt, yb) = (screen_luminance(text), screen_luminance(background));
+    if (yb - yt).abs() < DELTA_Y_MIN {return 0.0}
+
+    if yb > yt
+    {
+        let sapc = (yb.powf(NORM_BG) - yt.powf(NORM_TEXT)) * SCALE;
+        if sapc < LOW_CLIP {0.0} else {(sapc - LOW_OFFSET) * 100.0}
+    }
+
+    else
+    {
+        let sapc = (yb.powf(REV_BG) - yt.powf(REV_TEXT)) * SCALE;
+        if sapc > -LOW_CLIP {0.0} else {(sapc + LOW_OFFSET) * 100.0}
+    }
+}
+
+/// Gets the minimum font size in px
+/// that is readable at an Lc value
+/// with a font weight from 100 to 900.
+///
+/// The polarity is ignored and the weight
+/// is rounded to the nearest hundred.
+///
+/// Returns None if the contrast is
+/// too low for text.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::apca_min_font_size;
+/// assert_eq!(apca_min_font_size(75.0, 400), Some(18.0));
+/// assert_eq!(apca_min_font_size(-90.0, 700), Some(14.0));
+/// assert_eq!(apca_min_font_size(20.0, 400), None);
+/// ```
+pub fn apca_min_font_size(lc: f64, weight: u16) -> Option<f64>
+{
+    let column = weight_column(weight);
+
+    // Use the row at or below the Lc value
+    let row = FONT_SIZES.iter().rev().find(|(l, _)| *l <= lc.abs())?;
+    let size = row.1[column];
+    if size < NON_TEXT {Some(size)} else {None}
+}
+
+/// Gets the minimum Lc value that makes
+/// a font size in px and a weight
+/// from 100 to 900 readable.
+///
+/// Returns None if the font is too
+/// small for any contrast.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::apca_min_contrast;
+/// assert_eq!(apca_min_contrast(16.0, 400), Some(90.0));
+/// assert_eq!(apca_min_contrast(24.0, 700), Some(45.0));
+/// assert_eq!(apca_min_contrast(8.0, 400), None);
+/// ```
+pub fn apca_min_contrast(font_size: f64, weight: u16) -> Option<f64>
+{
+    let column = weight_column(weight);
+    FONT_SIZES.iter()
+        .find(|(_, sizes)| sizes[column] < NON_TEXT && sizes[column] <= font_size)
+        .map(|(l, _)| *l)
+}
+
+// Gets the table column of a font weight
+fn weight_column(weight: u16) -> usize
+{
+    let weight = (f64::from(weight) / 100.0).round().clamp(1.0, 9.0);
+    weight as usize - 1
+}
+
+// Gets the APCA screen luminance,
+// with a soft clamp near black
+fn screen_luminance(t: (u8, u8, u8)) -> f64
+{
+    let f = |n: u8| (f64::from(n) / 255.0).powf(MAIN_TRC);
+    let y = R_COEFFICIENT * f(t.0) + G_COEFFICIENT * f(t.1) + B_COEFFICIENT * f(t.2);
+    if y < BLACK_THRESHOLD {y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)} else {y}
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::parse_color;
+
+    #[test]
+    fn apca_test()
+    {
+        // Sample pairs of the APCA-W3 reference
+        let samples =
+        [
+            ("#888", "#fff", 63.056469930209424),
+            ("#fff", "#888", -68.54146436644962),
+            ("#000", "#aaa", 58.146262578561334),
+            ("#aaa", "#000", -56.24113336839742),
+            ("#123", "#def", 91.66830811481631),
+            ("#def", "#123", -93.06770049484275),
+            ("#123", "#444", 8.32326136957393),
+            ("#444", "#123", -7.526878460278154)
+        ];
+
+        for (text, background, expected) in samples.iter()
+        {
+            let lc = apca_contrast(parse_color(text, (0, 0, 0)), parse_color(background, (0, 0, 0)));
+            assert!((lc - expected).abs() < 1e-9, "{} on {}", text, background);
+        }
+
+        // Low contrasts are clipped
+        assert_eq!(apca_contrast((100, 100, 100), (110, 110, 110)), 0.0);
+        assert_eq!(apca_contrast((50, 50, 50), (50, 50, 50)), 0.0);
+
+        assert_eq!(Polarity::from_lc(apca_contrast((0, 0, 0), (255, 255, 255))), Some(Polarity::DarkOnLight));
+        assert_eq!(Polarity::from_lc(0.0), None);
+    }
+
+    #[test]
+    fn font_test()
+    {
+        assert_eq!(apca_min_font_size(90.0, 400), Some(16.0));
+        assert_eq!(apca_min_font_size(92.0, 400), Some(16.0));
+        assert_eq!(apca_min_font_size(60.0, 700), Some(16.0));
+        assert_eq!(apca_min_font_size(60.0, 900), Some(18.0));
+        assert_eq!(apca_min_font_size(60.0, 420), Some(24.0));
+        assert_eq!(apca_min_font_size(15.0, 900), None);
+        assert_eq!(apca_min_font_size(200.0, 100), Some(32.0));
+
+        assert_eq!(apca_min_contrast(18.0, 400), Some(75.0));
+        assert_eq!(apca_min_contrast(14.0, 700), Some(75.0));
+        assert_eq!(apca_min_contrast(120.0, 100), Some(40.0));
+        assert_eq!(apca_min_contrast(1000.0, 100), Some(40.0));
+        assert_eq!(apca_min_contrast(9.0, 700), None);
+        assert_eq!(apca_min_contrast(16.0, 0), apca_min_contrast(16.0, 100));
+    }
+}
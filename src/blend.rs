@@ -0,0 +1,281 @@
+use crate::*;
+
+/// A blend mode from the W3C Compositing
+/// and Blending spec, like the CSS mix-blend-mode.
+///
+/// The first modes are separable and work on
+/// each channel on its own.
+///
+/// Hue, Saturation, Color and Luminosity are
+/// non separable and mix the hue, saturation
+/// and luminosity of both colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum BlendMode
+{
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity
+}
+
+/// Blends a source color on top of
+/// a backdrop color with a blend mode.
+///
+/// Both colors are opaque, check blend_rgba
+/// to blend colors with alpha.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{blend, BlendMode};
+/// let c = blend((200, 100, 50), (128, 128, 128), BlendMode::Multiply);
+/// assert_eq!(c, (100, 50, 25));
+/// ```
+pub fn blend(backdrop: (u8, u8, u8), source: (u8, u8, u8), mode: BlendMode) -> (u8, u8, u8)
+{
+    unit_rgb_to_tuple(blend_unit(to_unit(backdrop), to_unit(source), mode))
+}
+
+/// Blends an RGBA source on top of
+/// an RGBA backdrop with a blend mode.
+///
+/// The blended color is weighted by the
+/// backdrop alpha and then composited
+/// with the source-over operator, as the
+/// W3C spec does.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{blend_rgba, BlendMode};
+/// let c = blend_rgba((255, 255, 255, 255), (255, 0, 0, 128), BlendMode::Multiply);
+/// assert_eq!(c, (255, 127, 127, 255));
+/// ```
+pub fn blend_rgba(backdrop: (u8, u8, u8, u8), source: (u8, u8, u8, u8), mode: BlendMode) -> (u8, u8, u8, u8)
+{
+    let ab = f64::from(backdrop.3) / 255.0;
+    let a_s = f64::from(source.3) / 255.0;
+    let ao = a_s + ab * (1.0 - a_s);
+    if ao <= 0.0 {return (0, 0, 0, 0)}
+
+    let cb = to_unit((backdrop.0, backdrop.1, backdrop.2));
+    let cs = to_unit((source.0, source.1, source.2));
+    let mixed = blend_unit(cb, cs, mode);
+
+    let f = |b: f64, s: f64, m: f64|
+    {
+        let s = (1.0 - ab) * s + ab * m;
+        (s * a_s + b * ab * (1.0 - a_s)) / ao
+    };
+
+    let c = unit_rgb_to_tuple((f(cb.0, cs.0, mixed.0), f(cb.1, cs.1, mixed.1), f(cb.2, cs.2, mixed.2)));
+    (c.0, c.1, c.2, alpha_to_u8(ao))
+}
+
+// Blends two colors with values from 0 to 1
+fn blend_unit(cb: (f64, f64, f64), cs: (f64, f64, f64), mode: BlendMode) -> (f64, f64, f64)
+{
+    let separable = |f: fn(f64, f64) -> f64| (f(cb.0, cs.0), f(cb.1, cs.1), f(cb.2, cs.2));
+
+    match mode
+    {
+        BlendMode::Normal => cs,
+        BlendMode::Multiply => separable(|b, s| b * s),
+        BlendMode::Screen => separable(screen),
+        BlendMode::Overlay => separable(|b, s| hard_light(s, b)),
+        BlendMode::Darken => separable(f64::min),
+        BlendMode::Lighten => separable(f64::max),
+        BlendMode::ColorDodge => separable(color_dodge),
+        BlendMode::ColorBurn => separable(color_burn),
+        BlendMode::HardLight => separable(hard_light),
+        BlendMode::SoftLight => separable(soft_light),
+        BlendMode::Difference => separable(|b, s| (b - s).abs()),
+        BlendMode::Exclusion => separable(|b, s| b + s - 2.0 * b * s),
+        BlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+        BlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+        BlendMode::Color => set_lum(cs, lum(cb)),
+        BlendMode::Luminosity => set_lum(cb, lum(cs))
+    }
+}
+
+fn screen(b: f64, s: f64) -> f64
+{
+    b + s - b * s
+}
+
+fn hard_light(b: f64, s: f64) -> f64
+{
+    if s <= 0.5 {b * 2.0 * s} else {screen(b, 2.0 * s - 1.0)}
+}
+
+fn color_dodge(b: f64, s: f64) -> f64
+{
+    if b == 0.0 {0.0}
+    else if s >= 1.0 {1.0}
+    else {(b / (1.0 - s)).min(1.0)}
+}
+
+fn color_burn(b: f64, s: f64) -> f64
+{
+    if b >= 1.0 {1.0}
+    else if s == 0.0 {0.0}
+    else {1.0 - ((1.0 - b) / s).min(1.0)}
+}
+
+#[allow(clippy::suspicious_else_formatting)]
+fn soft_light(b: f64, s: f64) -> f64
+{
+    if s <= 0.5
+    {
+        b - (1.0 - 2.0 * s) * b * (1.0 - b)
+    }
+
+    else
+    {
+        let d = if b <= 0.25 {((16.0 * b - 12.0) * b + 4.0) * b} else {b.sqrt()};
+        b + (2.0 * s - 1.0) * (d - b)
+    }
+}
+
+// Luminosity as defined by the spec
+fn lum(c: (f64, f64, f64)) -> f64
+{
+    0.3 * c.0 + 0.59 * c.1 + 0.11 * c.2
+}
+
+// Brings the channels back into 0 to 1
+// keeping the luminosity
+fn clip_color(c: (f64, f64, f64)) -> (f64, f64, f64)
+{
+    let l = lum(c);
+    let n = c.0.min(c.1).min(c.2);
+    let x = c.0.max(c.1).max(c.2);
+
+    let f = |v: f64|
+    {
+        let mut v = v;
+        if n < 0.0 {v = l + (v - l) * l / (l - n)}
+        if x > 1.0 {v = l + (v - l) * (1.0 - l) / (x - l)}
+        v
+    };
+
+    (f(c.0), f(c.1), f(c.2))
+}
+
+fn set_lum(c: (f64, f64, f64), l: f64) -> (f64, f64, f64)
+{
+    let d = l - lum(c);
+    clip_color((c.0 + d, c.1 + d, c.2 + d))
+}
+
+fn sat(c: (f64, f64, f64)) -> f64
+{
+    c.0.max(c.1).max(c.2) - c.0.min(c.1).min(c.2)
+}
+
+#[allow(clippy::suspicious_else_formatting)]
+fn set_sat(c: (f64, f64, f64), s: f64) -> (f64, f64, f64)
+{
+    let mut v = [c.0, c.1, c.2];
+    let mut order = [0, 1, 2];
+    order.sort_by(|a, b| v[*a].total_cmp(&v[*b]));
+    let (min, mid, max) = (order[0], order[1], order[2]);
+
+    if v[max] > v[min]
+    {
+        v[mid] = (v[mid] - v[min]) * s / (v[max] - v[min]);
+        v[max] = s;
+    }
+
+    else
+    {
+        v[mid] = 0.0;
+        v[max] = 0.0;
+    }
+
+    v[min] = 0.0;
+    (v[0], v[1], v[2])
+}
+
+fn to_unit(t: (u8, u8, u8)) -> (f64, f64, f64)
+{
+    let f = |n: u8| f64::from(n) / 255.0;
+    (f(t.0), f(t.1), f(t.2))
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn separable_test()
+    {
+        let (b, s) = ((200, 100, 50), (128, 128, 128));
+        assert_eq!(blend(b, s, BlendMode::Normal), s);
+        assert_eq!(blend(b, s, BlendMode::Multiply), (100, 50, 25));
+        assert_eq!(blend(b, s, BlendMode::Screen), (228, 178, 153));
+        assert_eq!(blend(b, s, BlendMode::Darken), (128, 100, 50));
+        assert_eq!(blend(b, s, BlendMode::Lighten), (200, 128, 128));
+        assert_eq!(blend(b, s, BlendMode::Difference), (72, 28, 78));
+        assert_eq!(blend(b, s, BlendMode::Exclusion), (127, 128, 128));
+
+        // Overlay is hard light with the colors swapped
+        assert_eq!(blend(b, s, BlendMode::Overlay), blend(s, b, BlendMode::HardLight));
+        assert_eq!(blend((255, 0, 64), (64, 64, 64), BlendMode::HardLight), (128, 0, 32));
+        assert_eq!(blend((255, 0, 64), (64, 64, 64), BlendMode::Overlay), (255, 0, 32));
+
+        assert_eq!(blend((0, 100, 255), (255, 128, 0), BlendMode::ColorDodge), (0, 201, 255));
+        assert_eq!(blend((0, 100, 255), (255, 128, 0), BlendMode::ColorBurn), (0, 0, 255));
+        assert_eq!(blend((64, 128, 192), (0, 128, 255), BlendMode::SoftLight), (16, 128, 221));
+    }
+
+    #[test]
+    fn non_separable_test()
+    {
+        let red = (255, 0, 0);
+        let gray = (128, 128, 128);
+
+        // A gray source has no hue or saturation
+        assert_eq!(blend(red, gray, BlendMode::Saturation), (77, 77, 77));
+        assert_eq!(blend(gray, red, BlendMode::Luminosity), (77, 77, 77));
+        assert_eq!(blend(red, gray, BlendMode::Color), (77, 77, 77));
+
+        // The luminosity of the backdrop is kept
+        let c = blend(gray, red, BlendMode::Color);
+        assert!((lum(to_unit(c)) - lum(to_unit(gray))).abs() < 0.01);
+        assert_eq!(blend(gray, red, BlendMode::Hue), gray);
+
+        // With a fully saturated backdrop hue works like color
+        assert_eq!(blend((0, 144, 255), (255, 0, 0), BlendMode::Hue), blend((0, 144, 255), (255, 0, 0), BlendMode::Color));
+        assert_eq!(blend((30, 144, 255), (30, 144, 255), BlendMode::Luminosity), (30, 144, 255));
+    }
+
+    #[test]
+    fn rgba_test()
+    {
+        let white = (255, 255, 255, 255);
+        assert_eq!(blend_rgba(white, (255, 0, 0, 255), BlendMode::Multiply), (255, 0, 0, 255));
+        assert_eq!(blend_rgba(white, (255, 0, 0, 0), BlendMode::Multiply), white);
+        assert_eq!(blend_rgba(white, (255, 0, 0, 128), BlendMode::Normal), (255, 127, 127, 255));
+
+        // Without a backdrop the source is kept as is
+        assert_eq!(blend_rgba((0, 0, 0, 0), (30, 144, 255, 128), BlendMode::Multiply), (30, 144, 255, 128));
+        assert_eq!(blend_rgba((0, 0, 0, 0), (0, 0, 0, 0), BlendMode::Screen), (0, 0, 0, 0));
+    }
+}
@@ -0,0 +1,292 @@
+// The coefficients are kept as published
+#![allow(clippy::excessive_precision)]
+
+use crate::*;
+
+// Polynomial fits of the colormaps.
+// Each row holds the red, green and blue
+// coefficients of one power of t, from t^0 to t^6,
+// with the channels going from 0 to 1.
+
+// Fits of the matplotlib colormaps by Matt Zucker
+static VIRIDIS: [[f64; 3]; 7] =
+[
+    [0.2777273272234177, 0.005407344544966578, 0.3340998053353061],
+    [0.1050930431085774, 1.404613529898575, 1.384590162594685],
+    [-0.3308618287255563, 0.214847559468213, 0.09509516302823659],
+    [-4.634230498983486, -5.799100973351585, -19.33244095627987],
+    [6.228269936347081, 14.17993336680509, 56.69055260068105],
+    [4.776384997670288, -13.74514537774601, -65.35303263337234],
+    [-5.435455855934631, 4.645852612178535, 26.3124352495832]
+];
+
+static MAGMA: [[f64; 3]; 7] =
+[
+    [-0.002136485053939582, -0.000749655052795221, -0.005386127855323933],
+    [0.2516605407371642, 0.6775232436837668, 2.494026599312351],
+    [8.353717279216625, -3.577719514958484, 0.3144679030132573],
+    [-27.66873308576866, 14.26473078096533, -13.64921318813922],
+    [52.17613981234068, -27.94360607168351, 12.94416944238394],
+    [-50.76852536473588, 29.04658282127291, 4.23415299384598],
+    [18.65570506591883, -11.48977351997711, -5.601961508734096]
+];
+
+static INFERNO: [[f64; 3]; 7] =
+[
+    [0.0002189403691192265, 0.001651004631001012, -0.01948089843709184],
+    [0.1065134194856116, 0.5639564367884091, 3.932712388889277],
+    [11.60249308247187, -3.972853965665698, -15.9423941062914],
+    [-41.70399613139459, 17.43639888205313, 44.35414519872813],
+    [77.162935699427, -33.40235894210092, -81.80730925738993],
+    [-71.31942824499214, 32.62606426397723, 73.20951985803202],
+    [25.13112622477341, -12.24266895238567, -23.07032500287172]
+];
+
+static PLASMA: [[f64; 3]; 7] =
+[
+    [0.05873234392399702, 0.02333670892565664, 0.5433401826748754],
+    [2.176514634195958, 0.2383834171260182, 0.7539604599784036],
+    [-2.689460476458034, -7.455851135738909, 3.110799939717086],
+    [6.130348345893603, 42.3461881477227, -28.51885465332158],
+    [-11.10743619062271, -82.66631109428045, 60.13984767418263],
+    [10.02306557647065, 71.41361770095349, -54.07218655560067],
+    [-3.658713842777788, -22.93153465461149, 18.19190778539828]
+];
+
+// The d3-scale-chromatic fit of cividis
+static CIVIDIS: [[f64; 3]; 7] =
+[
+    [-0.0178039216, 0.1274117647, 0.3185882353],
+    [-0.1385882353, 0.6695294118, 1.734745098],
+    [9.3401176471, 0.2071372549, -9.7350196078],
+    [-25.108627451, -0.5155294118, 24.185254902],
+    [27.5479215686, 0.6924705882, -25.9409411765],
+    [-10.6296862745, -0.2641960784, 9.7085098039],
+    [0.0, 0.0, 0.0]
+];
+
+// The polynomial approximation of turbo
+// published by Google with the colormap
+static TURBO: [[f64; 3]; 7] =
+[
+    [0.13572138, 0.09140261, 0.10667330],
+    [4.61539260, 2.19418839, 12.64194608],
+    [-42.66032258, 4.84296658, -60.58204836],
+    [132.13108234, -14.18503333, 110.36276771],
+    [-152.94239396, 4.27729857, -89.90310912],
+    [59.28637943, 2.82956604, 27.34824973],
+    [0.0, 0.0, 0.0]
+];
+
+/// A built in scientific colormap.
+///
+/// Viridis, Magma, Inferno, Plasma and Cividis
+/// are perceptually uniform, and Cividis is
+/// also readable with color vision deficiencies.
+///
+/// Turbo is a smooth rainbow for
+/// when details matter more than uniformity.
+///
+/// The colors come from polynomial fits.
+/// Each channel stays within 5 units of the
+/// reference tables for Viridis, Magma and Plasma,
+/// 9 for Inferno and 13 for Cividis near its dark end.
+/// The Turbo fit is rougher, up to about 32 units,
+/// with darker ends than the reference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Colormap
+{
+    Viridis,
+    Magma,
+    Inferno,
+    Plasma,
+    Cividis,
+    Turbo
+}
+
+impl Colormap
+{
+    /// Gets the color for a value from 0 to 1.
+    ///
+    /// Values outside of the range are clamped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Colormap;
+    /// let c = Colormap::Viridis.sample(0.5);
+    /// ```
+    pub fn sample(&self, t: f64) -> RGB
+    {
+        let t = if t.is_nan() {0.0} else {t.clamp(0.0, 1.0)};
+        let coefficients = self.get_coefficients();
+        let mut v = [0.0; 3];
+
+        for (i, channel) in v.iter_mut().enumerate()
+        {
+            *channel = coefficients.iter().rev().fold(0.0, |acc, c| acc * t + c[i]);
+        }
+
+        RGB::from_tuple(unit_rgb_to_tuple((v[0], v[1], v[2])))
+    }
+
+    /// Gets the color for a value from 0 to 1
+    /// with the colormap reversed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Colormap;
+    /// assert_eq!(Colormap::Magma.sample_reversed(0.0), Colormap::Magma.sample(1.0));
+    /// ```
+    pub fn sample_reversed(&self, t: f64) -> RGB
+    {
+        self.sample(1.0 - t)
+    }
+
+    /// Gets a number of colors evenly
+    /// spaced from 0 to 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Colormap;
+    /// let colors = Colormap::Plasma.take(8);
+    /// ```
+    pub fn take(&self, n: usize) -> Vec<RGB>
+    {
+        let steps = n.saturating_sub(1).max(1) as f64;
+        (0..n).map(|i| self.sample(i as f64 / steps)).collect()
+    }
+
+    /// Gets a number of colors evenly
+    /// spaced from 1 to 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Colormap;
+    /// let colors = Colormap::Plasma.take_reversed(8);
+    /// ```
+    pub fn take_reversed(&self, n: usize) -> Vec<RGB>
+    {
+        let mut colors = self.take(n);
+        colors.reverse();
+        colors
+    }
+
+    /// Makes a gradient with a number
+    /// of stops taken from the colormap.
+    ///
+    /// More stops follow the colormap more closely.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Colormap;
+    /// let g = Colormap::Turbo.to_gradient(16);
+    /// ```
+    pub fn to_gradient(&self, stops: usize) -> Gradient
+    {
+        Gradient::from_colors(&self.take(stops.max(2)), ColorSpace::SRGB)
+    }
+
+    /// Gets the colormap with a name,
+    /// ignoring the case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Colormap;
+    /// assert_eq!(Colormap::from_name("Viridis"), Some(Colormap::Viridis));
+    /// ```
+    pub fn from_name(name: &str) -> Option<Colormap>
+    {
+        match &clean_string(name)[..]
+        {
+            "viridis" => Some(Colormap::Viridis),
+            "magma" => Some(Colormap::Magma),
+            "inferno" => Some(Colormap::Inferno),
+            "plasma" => Some(Colormap::Plasma),
+            "cividis" => Some(Colormap::Cividis),
+            "turbo" => Some(Colormap::Turbo),
+            _ => None
+        }
+    }
+
+    fn get_coefficients(&self) -> &'static [[f64; 3]; 7]
+    {
+        match self
+        {
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Magma => &MAGMA,
+            Colormap::Inferno => &INFERNO,
+            Colormap::Plasma => &PLASMA,
+            Colormap::Cividis => &CIVIDIS,
+            Colormap::Turbo => &TURBO
+        }
+    }
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn colormap_test()
+    {
+        // Reference colors at 0, 1/9, 2/9 ... 1 with
+        // the largest channel error of each fit
+        let references =
+        [
+            (Colormap::Viridis, 4, ["440154", "482878", "3e4989", "31688e", "26828e", "1f9e89", "35b779", "6ece58", "b5de2b", "fde725"]),
+            (Colormap::Magma, 5, ["000004", "180f3e", "451077", "721f81", "9f2f7f", "cd4071", "f1605d", "fd9567", "fec98d", "fcfdbf"]),
+            (Colormap::Inferno, 9, ["000004", "1b0c42", "4b0c6b", "781c6d", "a52c60", "cf4446", "ed6925", "fb9a06", "f7d03c", "fcffa4"]),
+            (Colormap::Plasma, 5, ["0d0887", "47039f", "7301a8", "9c179e", "bd3786", "d8576b", "ed7953", "fa9e3b", "fdc926", "f0f921"]),
+            (Colormap::Cividis, 13, ["00204d", "00336f", "39486b", "575c6d", "707173", "8a8779", "a69d75", "c4b56c", "e4cf5b", "ffea46"]),
+            (Colormap::Turbo, 32, ["30123b", "4662d7", "36aaf9", "1ae4b6", "72fe5e", "c7ef34", "faba39", "f66b19", "cb2a04", "7a0403"])
+        ];
+
+        for (map, tolerance, hexes) in references.iter()
+        {
+            for (c, hex) in map.take(10).iter().zip(hexes.iter())
+            {
+                let r = parse_color(hex, (0, 0, 0));
+                let t = c.get_tuple();
+                assert!((i16::from(t.0) - i16::from(r.0)).abs() <= *tolerance, "{:?} {}", map, hex);
+                assert!((i16::from(t.1) - i16::from(r.1)).abs() <= *tolerance, "{:?} {}", map, hex);
+                assert!((i16::from(t.2) - i16::from(r.2)).abs() <= *tolerance, "{:?} {}", map, hex);
+            }
+        }
+
+        assert_eq!(Colormap::Cividis.sample(0.0), RGB::new(0, 32, 81));
+        assert_eq!(Colormap::Cividis.sample(1.0), RGB::new(253, 234, 69));
+        assert_eq!(Colormap::Turbo.sample(0.0), RGB::new(35, 23, 27));
+        assert_eq!(Colormap::Turbo.sample(-1.0), Colormap::Turbo.sample(0.0));
+        assert_eq!(Colormap::Turbo.sample(2.0), Colormap::Turbo.sample(1.0));
+    }
+
+    #[test]
+    fn take_test()
+    {
+        let colors = Colormap::Inferno.take(5);
+        assert_eq!(colors.len(), 5);
+        assert_eq!(colors[0], Colormap::Inferno.sample(0.0));
+        assert_eq!(colors[4], Colormap::Inferno.sample(1.0));
+
+        let mut reversed = Colormap::Inferno.take_reversed(5);
+        reversed.reverse();
+        assert_eq!(reversed, colors);
+        assert_eq!(Colormap::Inferno.sample_reversed(0.25), Colormap::Inferno.sample(0.75));
+        assert!(Colormap::Inferno.take(0).is_empty());
+
+        let g = Colormap::Plasma.to_gradient(5);
+        assert_eq!(g.take(5), Colormap::Plasma.take(5));
+
+        assert_eq!(Colormap::from_name(" MAGMA "), Some(Colormap::Magma));
+        assert_eq!(Colormap::from_name("jet"), None);
+    }
+}
@@ -0,0 +1,277 @@
+use crate::*;
+
+/// A WCAG 2 contrast requirement.
+///
+/// AA and AAA are for normal text,
+/// AALarge and AAALarge are for large text
+/// (at least 18pt, or 14pt and bold),
+/// and NonText is for user interface
+/// components and graphics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContrastLevel
+{
+    AA,
+    AALarge,
+    AAA,
+    AAALarge,
+    NonText
+}
+
+impl ContrastLevel
+{
+    /// Gets the minimum contrast ratio
+    /// needed to meet the level.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::ContrastLevel;
+    /// assert_eq!(ContrastLevel::AA.get_min_ratio(), 4.5);
+    /// ```
+    pub fn get_min_ratio(&self) -> f64
+    {
+        match self
+        {
+            ContrastLevel::AA => 4.5,
+            ContrastLevel::AALarge => 3.0,
+            ContrastLevel::AAA => 7.0,
+            ContrastLevel::AAALarge => 4.5,
+            ContrastLevel::NonText => 3.0
+        }
+    }
+}
+
+/// Gets the WCAG 2 relative luminance of a color,
+/// from 0 for black to 1 for white.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::relative_luminance;
+/// assert_eq!(relative_luminance((255, 255, 255)), 1.0);
+/// ```
+pub fn relative_luminance(t: (u8, u8, u8)) -> f64
+{
+    let (r, g, b) = rgb_to_linear(t);
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Gets the WCAG 2 contrast ratio of two colors,
+/// from 1 for the same color to 21 for black and white.
+///
+/// The order of the colors doesn't matter.
+/// The ratio is not rounded, as WCAG
+/// thresholds must not be rounded up.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::contrast_ratio;
+/// assert_eq!(contrast_ratio((0, 0, 0), (255, 255, 255)), 21.0);
+/// ```
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64
+{
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Checks if two colors have enough
+/// contrast to meet a WCAG 2 level.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{meets_contrast, ContrastLevel};
+/// assert!(meets_contrast((118, 118, 118), (255, 255, 255), ContrastLevel::AA));
+/// assert!(!meets_contrast((119, 119, 119), (255, 255, 255), ContrastLevel::AA));
+/// ```
+pub fn meets_contrast(a: (u8, u8, u8), b: (u8, u8, u8), level: ContrastLevel) -> bool
+{
+    contrast_ratio(a, b) >= level.get_min_ratio()
+}
+
+/// Gets the WCAG 2 levels met by two colors,
+/// from the least to the most strict.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{contrast_levels, ContrastLevel};
+/// let levels = contrast_levels((0, 0, 0), (128, 128, 128));
+/// assert_eq!(levels, vec![ContrastLevel::AALarge, ContrastLevel::NonText, ContrastLevel::AA, ContrastLevel::AAALarge]);
+/// ```
+pub fn contrast_levels(a: (u8, u8, u8), b: (u8, u8, u8)) -> Vec<ContrastLevel>
+{
+    let ratio = contrast_ratio(a, b);
+
+    let levels =
+    [
+        ContrastLevel::AALarge,
+        ContrastLevel::NonText,
+        ContrastLevel::AA,
+        ContrastLevel::AAALarge,
+        ContrastLevel::AAA
+    ];
+
+    levels.iter().copied().filter(|l| ratio >= l.get_min_ratio()).collect()
+}
+
+/// Picks the candidate text color with the
+/// highest WCAG 2 contrast on a background.
+///
+/// The first one wins a tie, and
+/// None is returned if there are no candidates.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::best_text_color;
+/// let c = best_text_color((30, 144, 255), &[(255, 255, 255), (0, 0, 0)]);
+/// assert_eq!(c, Some((0, 0, 0)));
+/// ```
+pub fn best_text_color(background: (u8, u8, u8), candidates: &[(u8, u8, u8)]) -> Option<(u8, u8, u8)>
+{
+    let mut best: Option<((u8, u8, u8), f64)> = None;
+
+    for c in candidates.iter()
+    {
+        let ratio = contrast_ratio(*c, background);
+
+        match best
+        {
+            Some((_, r)) if ratio <= r => {},
+            _ => best = Some((*c, ratio))
+        }
+    }
+
+    best.map(|(c, _)| c)
+}
+
+/// Makes a foreground color darker or lighter
+/// until it has a WCAG 2 contrast ratio of
+/// at least the target on a background.
+///
+/// The lightness changes in OKLCh, so the hue is kept
+/// and the chroma is only lowered to stay in gamut.
+/// A color darker than the background gets darker
+/// unless only lighter can meet the target,
+/// and the change is as small as possible.
+///
+/// If the target can't be met, the result is
+/// the color with the most contrast that way,
+/// usually black or white.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{ensure_contrast, contrast_ratio};
+/// let c = ensure_contrast((70, 130, 180), (255, 255, 255), 4.5);
+/// assert!(contrast_ratio(c, (255, 255, 255)) >= 4.5);
+/// ```
+pub fn ensure_contrast(fg: (u8, u8, u8), bg: (u8, u8, u8), target: f64) -> (u8, u8, u8)
+{
+    if contrast_ratio(fg, bg) >= target {return fg}
+    let change = |darker: bool, amount: f64| change_color_lightness_with(fg, darker, amount, LightnessModel::OKLCh);
+
+    // Go the way of the current contrast first,
+    // unless only the other way reaches the target
+    let mut darker = relative_luminance(fg) <= relative_luminance(bg);
+    let best = |darker: bool| contrast_ratio(change(darker, 100.0), bg);
+
+    if best(darker) < target && best(!darker) > best(darker)
+    {
+        darker = !darker;
+    }
+
+    if best(darker) < target {return change(darker, 100.0)}
+
+    // Search the smallest amount that meets the target
+    let (mut low, mut high) = (0.0, 100.0);
+
+    for _ in 0..24
+    {
+        let middle = (low + high) / 2.0;
+        if contrast_ratio(change(darker, middle), bg) >= target {high = middle} else {low = middle}
+    }
+
+    change(darker, high)
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn luminance_test()
+    {
+        assert_eq!(relative_luminance((0, 0, 0)), 0.0);
+        assert_eq!(relative_luminance((255, 255, 255)), 1.0);
+        assert!((relative_luminance((255, 0, 0)) - 0.2126).abs() < 1e-9);
+        assert!((relative_luminance((128, 128, 128)) - 0.2158605).abs() < 1e-6);
+    }
+
+    #[test]
+    fn contrast_test()
+    {
+        let white = (255, 255, 255);
+        assert_eq!(contrast_ratio(white, white), 1.0);
+        assert_eq!(contrast_ratio((255, 255, 255), (0, 0, 0)), 21.0);
+        assert!((contrast_ratio((118, 118, 118), white) - 4.54).abs() < 0.01);
+        assert!((contrast_ratio(white, (0, 0, 255)) - 8.59).abs() < 0.01);
+        assert!((contrast_ratio((255, 0, 0), white) - 4.0).abs() < 0.01);
+
+        // #767676 is the lightest gray that meets AA on white
+        assert!(meets_contrast((118, 118, 118), white, ContrastLevel::AA));
+        assert!(!meets_contrast((119, 119, 119), white, ContrastLevel::AA));
+        assert!(meets_contrast((255, 0, 0), white, ContrastLevel::AALarge));
+        assert!(meets_contrast((255, 0, 0), white, ContrastLevel::NonText));
+        assert!(!meets_contrast((255, 0, 0), white, ContrastLevel::AAALarge));
+        assert!(meets_contrast((0, 0, 255), white, ContrastLevel::AAA));
+
+        assert_eq!(contrast_levels(white, white), vec![]);
+        assert_eq!(contrast_levels((0, 0, 0), white).len(), 5);
+    }
+
+    #[test]
+    fn text_color_test()
+    {
+        let candidates = [(255, 255, 255), (0, 0, 0), (255, 255, 0)];
+        assert_eq!(best_text_color((0, 0, 139), &candidates), Some((255, 255, 255)));
+        assert_eq!(best_text_color((255, 215, 0), &candidates), Some((0, 0, 0)));
+        assert_eq!(best_text_color((128, 128, 128), &[(1, 1, 1), (1, 1, 1)]), Some((1, 1, 1)));
+        assert_eq!(best_text_color((0, 0, 0), &[]), None);
+    }
+
+    #[test]
+    fn ensure_contrast_test()
+    {
+        let (white, black) = ((255, 255, 255), (0, 0, 0));
+        let blue = (70, 130, 180);
+        assert_eq!(ensure_contrast(blue, white, 3.0), blue);
+
+        // Darker on white, keeping the hue
+        let c = ensure_contrast(blue, white, 4.5);
+        let ratio = contrast_ratio(c, white);
+        assert!((4.5..4.7).contains(&ratio));
+        let hue = OKLCh::from(RGB::from_tuple(blue)).get_h();
+        assert!((OKLCh::from(RGB::from_tuple(c)).get_h() - hue).abs() < 3.0);
+
+        // Lighter on black
+        let c = ensure_contrast((20, 40, 80), black, 7.0);
+        assert!(contrast_ratio(c, black) >= 7.0);
+        assert!(relative_luminance(c) > relative_luminance((20, 40, 80)));
+
+        // A dark color on a mid gray goes lighter
+        // when darker can't reach the target
+        let gray = (110, 110, 110);
+        let c = ensure_contrast((90, 90, 90), gray, 4.5);
+        assert!(contrast_ratio(c, gray) >= 4.5);
+        assert!(relative_luminance(c) > relative_luminance(gray));
+
+        // Impossible targets give the most contrast
+        assert_eq!(ensure_contrast(blue, white, 25.0), black);
+    }
+}
@@ -0,0 +1,304 @@
+use crate::*;
+
+/// A color difference formula (Delta E).
+///
+/// A difference of about 1 is the smallest
+/// one people can notice, and 2 to 3 is easy
+/// to see when the colors are side by side.
+///
+/// CIE76 is the distance in Lab.
+///
+/// CIE94 corrects the chroma and hue weights
+/// with the constants for graphic arts or textiles.
+///
+/// CIEDE2000 is the most accurate and
+/// the one to use when unsure.
+///
+/// CMC(l, c) is the textile industry formula,
+/// with a lightness and a chroma weight,
+/// usually 2:1 for acceptability
+/// and 1:1 for perceptibility.
+///
+/// CIE94 and CMC are not symmetric,
+/// the first color is the reference.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum Metric
+{
+    CIE76,
+    CIE94GraphicArts,
+    CIE94Textiles,
+    #[default]
+    CIEDE2000,
+    CMC(f64, f64)
+}
+
+/// Gets the difference between two Lab colors.
+///
+/// If the colors have different reference whites
+/// the second one is converted to the white of the first.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{delta_e, Lab, Metric};
+/// let a = Lab::new(50.0, 2.6772, -79.7751);
+/// let b = Lab::new(50.0, 0.0, -82.7485);
+/// assert!((delta_e(&a, &b, Metric::CIEDE2000) - 2.0425).abs() < 0.0001);
+/// ```
+pub fn delta_e(a: &Lab, b: &Lab, metric: Metric) -> f64
+{
+    let b = if a.get_white() == b.get_white() {*b} else {b.to_white(a.get_white())};
+    let (c1, c2) = (a.get_tuple(), b.get_tuple());
+
+    match metric
+    {
+        Metric::CIE76 => cie76(c1, c2),
+        Metric::CIE94GraphicArts => cie94(c1, c2, 1.0, 0.045, 0.015),
+        Metric::CIE94Textiles => cie94(c1, c2, 2.0, 0.048, 0.014),
+        Metric::CIEDE2000 => ciede2000(c1, c2),
+        Metric::CMC(l, c) => cmc(c1, c2, l, c)
+    }
+}
+
+/// Gets the difference between two RGB tuples,
+/// using their D65 Lab values.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{delta_e_rgb, Metric};
+/// let d = delta_e_rgb((0, 71, 171), (0, 75, 165), Metric::CIEDE2000);
+/// assert!(d < 3.0);
+/// ```
+pub fn delta_e_rgb(a: (u8, u8, u8), b: (u8, u8, u8), metric: Metric) -> f64
+{
+    delta_e(&Lab::from(RGB::from_tuple(a)), &Lab::from(RGB::from_tuple(b)), metric)
+}
+
+fn cie76(c1: (f64, f64, f64), c2: (f64, f64, f64)) -> f64
+{
+    ((c1.0 - c2.0).powi(2) + (c1.1 - c2.1).powi(2) + (c1.2 - c2.2).powi(2)).sqrt()
+}
+
+// Gets the lightness, chroma and squared hue differences
+fn differences(c1: (f64, f64, f64), c2: (f64, f64, f64)) -> (f64, f64, f64)
+{
+    let (ch1, ch2) = (c1.1.hypot(c1.2), c2.1.hypot(c2.2));
+    let dc = ch1 - ch2;
+    let dh2 = (c1.1 - c2.1).powi(2) + (c1.2 - c2.2).powi(2) - dc * dc;
+    (c1.0 - c2.0, dc, dh2.max(0.0))
+}
+
+fn cie94(c1: (f64, f64, f64), c2: (f64, f64, f64), kl: f64, k1: f64, k2: f64) -> f64
+{
+    let (dl, dc, dh2) = differences(c1, c2);
+    let chroma = c1.1.hypot(c1.2);
+    let (sc, sh) = (1.0 + k1 * chroma, 1.0 + k2 * chroma);
+    ((dl / kl).powi(2) + (dc / sc).powi(2) + dh2 / (sh * sh)).sqrt()
+}
+
+fn cmc(c1: (f64, f64, f64), c2: (f64, f64, f64), l: f64, c: f64) -> f64
+{
+    let (dl, dc, dh2) = differences(c1, c2);
+    let (sl, sc, sh) = cmc_weights(c1);
+    ((dl / (l * sl)).powi(2) + (dc / (c * sc)).powi(2) + dh2 / (sh * sh)).sqrt()
+}
+
+// Gets the CMC lightness, chroma and hue
+// weights of the reference color
+#[allow(clippy::suspicious_else_formatting)]
+fn cmc_weights(c1: (f64, f64, f64)) -> (f64, f64, f64)
+{
+    let chroma = c1.1.hypot(c1.2);
+    let hue = c1.2.atan2(c1.1).to_degrees().rem_euclid(360.0);
+
+    let sl = if c1.0 < 16.0 {0.511} else {0.040975 * c1.0 / (1.0 + 0.01765 * c1.0)};
+    let sc = 0.0638 * chroma / (1.0 + 0.0131 * chroma) + 0.638;
+    let f = (chroma.powi(4) / (chroma.powi(4) + 1900.0)).sqrt();
+
+    let t = if (164.0..=345.0).contains(&hue)
+    {
+        0.56 + (0.2 * (hue + 168.0).to_radians().cos()).abs()
+    }
+
+    else
+    {
+        0.36 + (0.4 * (hue + 35.0).to_radians().cos()).abs()
+    };
+
+    (sl, sc, sc * (f * t + 1.0 - f))
+}
+
+// Gets factors s so that the difference between
+// a reference color and any color with a chroma
+// up to max_chroma is at least the CIE76 one / s,
+// and at least the lightness difference / the
+// first factor. The weights of CIE94 and CMC only
+// depend on the reference, for CIEDE2000 the largest
+// weights are used and the rotation term can remove
+// at most 1 - sqrt(3) / 2 of the chroma and hue part
+pub(crate) fn cie76_factors(reference: &Lab, metric: Metric, max_chroma: f64) -> (f64, f64)
+{
+    let c1 = reference.get_tuple();
+    let chroma = c1.1.hypot(c1.2);
+
+    let (sl, s) = match metric
+    {
+        Metric::CIE76 => (1.0, 1.0),
+        Metric::CIE94GraphicArts => (1.0, 1.0 + 0.045 * chroma),
+        Metric::CIE94Textiles => (2.0, (1.0 + 0.048 * chroma).max(2.0)),
+        Metric::CIEDE2000 =>
+        {
+            // SL is 1 at a mean L of 50 and largest
+            // at 0 or 100, where (L - 50)^2 is 2500,
+            // and a is scaled by at most 1.5
+            let sl = 1.0 + 0.015 * 2500.0 / 2520f64.sqrt();
+            let sc = 1.0 + 0.045 * 1.5 * (chroma + max_chroma) / 2.0;
+            (sl, sc / (1.0 - 3f64.sqrt() / 2.0).sqrt())
+        },
+        Metric::CMC(l, c) =>
+        {
+            let (sl, sc, sh) = cmc_weights(c1);
+            (l * sl, (l * sl).max(c * sc).max(sh))
+        }
+    };
+
+    (sl.min(s), s)
+}
+
+// CIEDE2000 following the notes of
+// Sharma, Wu and Dalal (2005)
+fn ciede2000(c1: (f64, f64, f64), c2: (f64, f64, f64)) -> f64
+{
+    let pow25 = 25f64.powi(7);
+    let mean_chroma = (c1.1.hypot(c1.2) + c2.1.hypot(c2.2)) / 2.0;
+    let g = 0.5 * (1.0 - (mean_chroma.powi(7) / (mean_chroma.powi(7) + pow25)).sqrt());
+
+    let (a1, a2) = ((1.0 + g) * c1.1, (1.0 + g) * c2.1);
+    let (ch1, ch2) = (a1.hypot(c1.2), a2.hypot(c2.2));
+
+    let hue = |a: f64, b: f64| if a == 0.0 && b == 0.0 {0.0} else {b.atan2(a).to_degrees().rem_euclid(360.0)};
+    let (h1, h2) = (hue(a1, c1.2), hue(a2, c2.2));
+    let chroma_product = ch1 * ch2;
+
+    let dh = if chroma_product == 0.0 {0.0}
+        else if (h2 - h1).abs() <= 180.0 {h2 - h1}
+        else if h2 - h1 > 180.0 {h2 - h1 - 360.0}
+        else {h2 - h1 + 360.0};
+
+    let dl = c2.0 - c1.0;
+    let dc = ch2 - ch1;
+    let dh = 2.0 * chroma_product.sqrt() * (dh / 2.0).to_radians().sin();
+
+    let mean_l = (c1.0 + c2.0) / 2.0;
+    let mean_c = (ch1 + ch2) / 2.0;
+
+    let mean_h = if chroma_product == 0.0 {h1 + h2}
+        else if (h1 - h2).abs() <= 180.0 {(h1 + h2) / 2.0}
+        else if h1 + h2 < 360.0 {(h1 + h2 + 360.0) / 2.0}
+        else {(h1 + h2 - 360.0) / 2.0};
+
+    let cos = |degrees: f64| degrees.to_radians().cos();
+    let t = 1.0 - 0.17 * cos(mean_h - 30.0) + 0.24 * cos(2.0 * mean_h)
+        + 0.32 * cos(3.0 * mean_h + 6.0) - 0.20 * cos(4.0 * mean_h - 63.0);
+
+    let d_theta = 30.0 * (-((mean_h - 275.0) / 25.0).powi(2)).exp();
+    let rc = 2.0 * (mean_c.powi(7) / (mean_c.powi(7) + pow25)).sqrt();
+    let sl = 1.0 + 0.015 * (mean_l - 50.0).powi(2) / (20.0 + (mean_l - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045 * mean_c;
+    let sh = 1.0 + 0.015 * mean_c * t;
+    let rt = -(2.0 * d_theta).to_radians().sin() * rc;
+
+    let (l, c, h) = (dl / sl, dc / sc, dh / sh);
+    (l * l + c * c + h * h + rt * c * h).sqrt()
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn ciede2000_test()
+    {
+        // The test data of Sharma, Wu and Dalal
+        let data =
+        [
+            ((50.0000, 2.6772, -79.7751), (50.0000, 0.0000, -82.7485), 2.0425),
+            ((50.0000, 3.1571, -77.2803), (50.0000, 0.0000, -82.7485), 2.8615),
+            ((50.0000, 2.8361, -74.0200), (50.0000, 0.0000, -82.7485), 3.4412),
+            ((50.0000, -1.3802, -84.2814), (50.0000, 0.0000, -82.7485), 1.0000),
+            ((50.0000, -1.1848, -84.8006), (50.0000, 0.0000, -82.7485), 1.0000),
+            ((50.0000, -0.9009, -85.5211), (50.0000, 0.0000, -82.7485), 1.0000),
+            ((50.0000, 0.0000, 0.0000), (50.0000, -1.0000, 2.0000), 2.3669),
+            ((50.0000, -1.0000, 2.0000), (50.0000, 0.0000, 0.0000), 2.3669),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0009), 7.1792),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0010), 7.1792),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0011), 7.2195),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0012), 7.2195),
+            ((50.0000, -0.0010, 2.4900), (50.0000, 0.0009, -2.4900), 4.8045),
+            ((50.0000, -0.0010, 2.4900), (50.0000, 0.0010, -2.4900), 4.8045),
+            ((50.0000, -0.0010, 2.4900), (50.0000, 0.0011, -2.4900), 4.7461),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 0.0000, -2.5000), 4.3065),
+            ((50.0000, 2.5000, 0.0000), (73.0000, 25.0000, -18.0000), 27.1492),
+            ((50.0000, 2.5000, 0.0000), (61.0000, -5.0000, 29.0000), 22.8977),
+            ((50.0000, 2.5000, 0.0000), (56.0000, -27.0000, -3.0000), 31.9030),
+            ((50.0000, 2.5000, 0.0000), (58.0000, 24.0000, 15.0000), 19.4535),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 3.1736, 0.5854), 1.0000),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 3.2972, 0.0000), 1.0000),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 1.8634, 0.5757), 1.0000),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 3.2592, 0.3350), 1.0000),
+            ((60.2574, -34.0099, 36.2677), (60.4626, -34.1751, 39.4387), 1.2644),
+            ((63.0109, -31.0961, -5.8663), (62.8187, -29.7946, -4.0864), 1.2630),
+            ((61.2901, 3.7196, -5.3901), (61.4292, 2.2480, -4.9620), 1.8731),
+            ((35.0831, -44.1164, 3.7933), (35.0232, -40.0716, 1.5901), 1.8645),
+            ((22.7233, 20.0904, -46.6940), (23.0331, 14.9730, -42.5619), 2.0373),
+            ((36.4612, 47.8580, 18.3852), (36.2715, 50.5065, 21.2231), 1.4146),
+            ((90.8027, -2.0831, 1.4410), (91.1528, -1.6435, 0.0447), 1.4441),
+            ((90.9257, -0.5406, -0.9208), (88.6381, -0.8985, -0.7239), 1.5381),
+            ((6.7747, -0.2908, -2.4247), (5.8714, -0.0985, -2.2286), 0.6377),
+            ((2.0776, 0.0795, -1.1350), (0.9033, -0.0636, -0.5514), 0.9082)
+        ];
+
+        for (c1, c2, expected) in data.iter()
+        {
+            let a = Lab::new(c1.0, c1.1, c1.2);
+            let b = Lab::new(c2.0, c2.1, c2.2);
+            assert!((delta_e(&a, &b, Metric::CIEDE2000) - expected).abs() < 0.0001, "{:?} {:?}", c1, c2);
+            assert!((delta_e(&b, &a, Metric::CIEDE2000) - expected).abs() < 0.0001, "{:?} {:?}", c2, c1);
+        }
+    }
+
+    #[test]
+    fn metric_test()
+    {
+        let a = Lab::new(50.0, 2.6772, -79.7751);
+        let b = Lab::new(50.0, 0.0, -82.7485);
+        let metrics = [Metric::CIE76, Metric::CIE94GraphicArts, Metric::CIE94Textiles, Metric::CIEDE2000, Metric::CMC(2.0, 1.0)];
+
+        for m in metrics.iter()
+        {
+            assert_eq!(delta_e(&a, &a, *m), 0.0);
+            assert!(delta_e(&a, &b, *m) > 0.0);
+        }
+
+        assert!((delta_e(&a, &b, Metric::CIE76) - 4.0011).abs() < 0.0001);
+        assert!((delta_e(&a, &b, Metric::CIE94GraphicArts) - 1.3950).abs() < 0.0001);
+        assert!((delta_e(&a, &b, Metric::CIE94Textiles) - 1.4230).abs() < 0.0001);
+        assert!((delta_e(&a, &b, Metric::CMC(1.0, 1.0)) - 1.7387).abs() < 0.0001);
+
+        // Only the lightness weight changes with l
+        let (c, d) = (Lab::new(40.0, 10.0, 10.0), Lab::new(45.0, 10.0, 10.0));
+        let ratio = delta_e(&c, &d, Metric::CMC(1.0, 1.0)) / delta_e(&c, &d, Metric::CMC(2.0, 1.0));
+        assert!((ratio - 2.0).abs() < 1e-9);
+
+        // The white of the first color is used
+        let d50 = a.to_white(WhitePoint::D50);
+        assert!(delta_e(&a, &d50, Metric::CIE76) < 1e-9);
+
+        assert_eq!(delta_e_rgb((255, 0, 0), (255, 0, 0), Metric::CIEDE2000), 0.0);
+        assert!(delta_e_rgb((255, 0, 0), (0, 0, 255), Metric::CIEDE2000) > 50.0);
+    }
+}
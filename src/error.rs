@@ -0,0 +1,105 @@
+use std::fmt;
+use std::ops::Range;
+
+/// The error returned by the strict parsing functions.
+///
+/// Every variant carries the byte span
+/// of the offending token in the input string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorError
+{
+    /// The input looked like a name
+    /// but it is not a known color.
+    UnknownName
+    {
+        name: String,
+        span: Range<usize>
+    },
+
+    /// A numeric component is outside
+    /// of its valid range.
+    OutOfRange
+    {
+        value: String,
+        span: Range<usize>
+    },
+
+    /// The input had the wrong
+    /// number of components.
+    ComponentCount
+    {
+        expected: usize,
+        found: usize,
+        span: Range<usize>
+    },
+
+    /// The input could not be understood.
+    Syntax
+    {
+        message: String,
+        span: Range<usize>
+    }
+}
+
+impl ColorError
+{
+    /// Returns the byte span of
+    /// the offending token.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::try_parse_color;
+    /// let err = try_parse_color("10, 300, 20", (0, 0, 0)).unwrap_err();
+    /// assert_eq!(err.span(), 4..7);
+    /// ```
+    pub fn span(&self) -> Range<usize>
+    {
+        match self
+        {
+            ColorError::UnknownName {span, ..} => span.clone(),
+            ColorError::OutOfRange {span, ..} => span.clone(),
+            ColorError::ComponentCount {span, ..} => span.clone(),
+            ColorError::Syntax {span, ..} => span.clone()
+        }
+    }
+}
+
+impl fmt::Display for ColorError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            ColorError::UnknownName {name, span} =>
+                write!(f, "unknown color name \"{}\" at {}..{}", name, span.start, span.end),
+            ColorError::OutOfRange {value, span} =>
+                write!(f, "value \"{}\" out of range at {}..{}", value, span.start, span.end),
+            ColorError::ComponentCount {expected, found, span} =>
+                write!(f, "expected {} components but found {} at {}..{}", expected, found, span.start, span.end),
+            ColorError::Syntax {message, span} =>
+                write!(f, "{} at {}..{}", message, span.start, span.end)
+        }
+    }
+}
+
+impl std::error::Error for ColorError {}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn error_test()
+    {
+        let e = ColorError::OutOfRange {value: "300".to_string(), span: 4..7};
+        assert_eq!(e.span(), 4..7);
+        assert_eq!(e.to_string(), "value \"300\" out of range at 4..7");
+
+        let e = ColorError::ComponentCount {expected: 3, found: 2, span: 0..5};
+        assert_eq!(e.to_string(), "expected 3 components but found 2 at 0..5");
+    }
+}
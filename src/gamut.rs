@@ -0,0 +1,261 @@
+use crate::*;
+
+// Largest OKLab distance that is not noticeable
+const JND: f64 = 0.02;
+
+// Precision of the chroma and lightness searches
+const SEARCH_EPSILON: f64 = 0.0001;
+
+// A chroma beyond any sRGB color, larger
+// ones are lowered to it before mapping
+const MAX_CHROMA: f64 = 0.5;
+
+// How far outside of 0 to 1 an sRGB value
+// can be while still counting as in gamut
+const GAMUT_EPSILON: f64 = 0.0001;
+
+/// The method used to bring colors
+/// outside of the sRGB gamut back into it.
+///
+/// Clip clamps each RGB channel, which is fast
+/// but can shift the hue and lightness a lot.
+///
+/// Chroma is the CSS Color 4 algorithm. It lowers
+/// the OKLCh chroma keeping the lightness and hue,
+/// and stops as soon as clipping is not noticeable.
+///
+/// MINDE (minimum delta E) keeps the hue and looks
+/// for the closest color on the gamut surface,
+/// so the lightness can change a little too.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamutMapping
+{
+    Clip,
+    Chroma,
+    MINDE
+}
+
+/// Checks if an OKLCh color is inside
+/// of the sRGB gamut.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{in_gamut, OKLCh};
+/// assert!(in_gamut(OKLCh::new(0.5, 0.05, 200.0)));
+/// assert!(!in_gamut(OKLCh::new(0.9, 0.3, 200.0)));
+/// ```
+pub fn in_gamut(c: OKLCh) -> bool
+{
+    let (r, g, b) = OKLab::from(c).to_linear_rgb();
+
+    [r, g, b].iter().all(|n|
+    {
+        let v = linear_to_srgb(*n);
+        (-GAMUT_EPSILON..=1.0 + GAMUT_EPSILON).contains(&v)
+    })
+}
+
+/// Converts an OKLCh color into an RGB
+/// bringing it into the sRGB gamut
+/// with the chosen method.
+///
+/// Any color space can be mapped by converting
+/// it to OKLCh first, values outside of the
+/// gamut survive the conversions.
+///
+/// Values that are not finite don't make a color,
+/// so a NaN lightness is read as 0, a NaN chroma
+/// or a NaN or infinite hue as no chroma, and a chroma above 0.5
+/// (including infinity) as 0.5, which is
+/// beyond any sRGB color.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{map_to_gamut, OKLCh, GamutMapping};
+/// let c = map_to_gamut(OKLCh::new(0.9, 0.3, 200.0), GamutMapping::Chroma);
+/// ```
+pub fn map_to_gamut(c: OKLCh, method: GamutMapping) -> RGB
+{
+    let (l, chroma, h) = c.get_tuple();
+    if l.is_nan() || l <= 0.0 {return RGB::new(0, 0, 0)}
+    if l >= 1.0 {return RGB::new(255, 255, 255)}
+    let chroma = if chroma.is_nan() || !h.is_finite() {0.0} else {chroma.min(MAX_CHROMA)};
+    let c = OKLCh::new(l, chroma, if h.is_finite() {h} else {0.0});
+    if in_gamut(c) {return RGB::from(c)}
+
+    match method
+    {
+        GamutMapping::Clip => RGB::from(c),
+        GamutMapping::Chroma => RGB::from(reduce_chroma(c)),
+        GamutMapping::MINDE => RGB::from(project(c))
+    }
+}
+
+// The CSS Color 4 chroma reduction algorithm
+#[allow(clippy::suspicious_else_formatting)]
+fn reduce_chroma(origin: OKLCh) -> OKLCh
+{
+    let (l, _, h) = origin.get_tuple();
+    let mut min = 0.0;
+    let mut max = origin.get_c();
+    let mut min_in_gamut = true;
+    let mut clipped = clip(origin);
+
+    if distance(clipped, origin) < JND {return clipped}
+
+    while max - min > SEARCH_EPSILON
+    {
+        let chroma = (min + max) / 2.0;
+        let current = OKLCh::new(l, chroma, h);
+
+        if min_in_gamut && in_gamut(current)
+        {
+            min = chroma;
+            continue
+        }
+
+        clipped = clip(current);
+        let e = distance(clipped, current);
+
+        if e < JND
+        {
+            if JND - e < SEARCH_EPSILON {return clipped}
+            min_in_gamut = false;
+            min = chroma;
+        }
+
+        else
+        {
+            max = chroma;
+        }
+    }
+
+    clipped
+}
+
+// Finds the color with the same hue on the
+// gamut surface that is closest to the origin
+fn project(origin: OKLCh) -> OKLCh
+{
+    let (l, c, h) = origin.get_tuple();
+
+    let candidate = |lightness: f64|
+    {
+        OKLCh::new(lightness, max_chroma(lightness, h).min(c), h)
+    };
+
+    // Golden section search over the lightness
+    let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+    let (mut a, mut b) = (0.0, 1.0);
+
+    while b - a > SEARCH_EPSILON
+    {
+        let x1 = b - ratio * (b - a);
+        let x2 = a + ratio * (b - a);
+
+        if distance(candidate(x1), origin) < distance(candidate(x2), origin) {b = x2}
+        else {a = x1}
+    }
+
+    let best = candidate((a + b) / 2.0);
+    let same_lightness = candidate(l);
+    if distance(same_lightness, origin) <= distance(best, origin) {same_lightness} else {best}
+}
+
+// Gets the highest chroma that is in gamut
+// for a lightness and a hue
+fn max_chroma(l: f64, h: f64) -> f64
+{
+    let (mut min, mut max) = (0.0, 0.5);
+
+    while max - min > SEARCH_EPSILON
+    {
+        let chroma = (min + max) / 2.0;
+        if in_gamut(OKLCh::new(l, chroma, h)) {min = chroma} else {max = chroma}
+    }
+
+    min
+}
+
+// Clamps the sRGB channels of a color
+fn clip(c: OKLCh) -> OKLCh
+{
+    let (r, g, b) = OKLab::from(c).to_linear_rgb();
+    let f = |n: f64| srgb_to_linear(linear_to_srgb(n).clamp(0.0, 1.0));
+    OKLCh::from(OKLab::from_linear_rgb((f(r), f(g), f(b))))
+}
+
+// Gets the euclidean distance in OKLab
+fn distance(a: OKLCh, b: OKLCh) -> f64
+{
+    let (l1, a1, b1) = OKLab::from(a).get_tuple();
+    let (l2, a2, b2) = OKLab::from(b).get_tuple();
+    ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn in_gamut_test()
+    {
+        assert!(in_gamut(OKLCh::from(RGB::new(255, 0, 0))));
+        assert!(in_gamut(OKLCh::from(RGB::new(255, 255, 255))));
+        assert!(!in_gamut(OKLCh::new(0.7, 0.4, 150.0)));
+        assert!(!in_gamut(OKLCh::from(OKLab::new(1.2, 0.0, 0.0))));
+    }
+
+    #[test]
+    fn map_test()
+    {
+        // Values that are not finite still give a color
+        for method in [GamutMapping::Clip, GamutMapping::Chroma, GamutMapping::MINDE].iter()
+        {
+            let rgb = map_to_gamut(OKLCh::new(0.5, f64::INFINITY, 30.0), *method);
+            assert!(in_gamut(OKLCh::from(rgb)));
+            assert_eq!(map_to_gamut(OKLCh::new(f64::NAN, 0.1, 30.0), *method), RGB::new(0, 0, 0));
+            assert_eq!(map_to_gamut(OKLCh::new(0.5, f64::NAN, 30.0), *method), map_to_gamut(OKLCh::new(0.5, 0.0, 0.0), *method));
+            assert_eq!(map_to_gamut(OKLCh::new(0.5, 0.1, f64::NAN), *method), map_to_gamut(OKLCh::new(0.5, 0.0, 0.0), *method));
+            assert_eq!(map_to_gamut(OKLCh::new(0.5, 0.1, f64::INFINITY), *method), map_to_gamut(OKLCh::new(0.5, 0.0, 0.0), *method));
+        }
+
+        assert_eq!(map_to_gamut(OKLCh::new(0.5, 1e300, 30.0), GamutMapping::Chroma),
+            map_to_gamut(OKLCh::new(0.5, 0.5, 30.0), GamutMapping::Chroma));
+
+        let c = OKLCh::new(0.9, 0.3, 250.0);
+
+        for method in [GamutMapping::Clip, GamutMapping::Chroma, GamutMapping::MINDE].iter()
+        {
+            let rgb = map_to_gamut(c, *method);
+            assert!(in_gamut(OKLCh::from(rgb)));
+        }
+
+        // Chroma reduction keeps the lightness and
+        // the hue shift is smaller than with clipping
+        let mapped = OKLCh::from(map_to_gamut(c, GamutMapping::Chroma));
+        assert!((mapped.get_l() - 0.9).abs() < 0.01);
+        assert!((mapped.get_h() - 250.0).abs() < 15.0);
+
+        // Clipping shifts the hue and lightness more
+        let clipped = OKLCh::from(map_to_gamut(c, GamutMapping::Clip));
+        assert!((clipped.get_h() - 250.0).abs() > (mapped.get_h() - 250.0).abs());
+        assert!((clipped.get_l() - 0.9).abs() > 0.05);
+
+        // MINDE keeps the hue and is closer than
+        // only lowering the chroma
+        let projected = OKLCh::from(map_to_gamut(c, GamutMapping::MINDE));
+        let reduced = OKLCh::new(0.9, max_chroma(0.9, 250.0), 250.0);
+        assert!((projected.get_h() - 250.0).abs() < 1.0);
+        assert!(distance(projected, c) < distance(reduced, c));
+
+        assert_eq!(map_to_gamut(OKLCh::new(1.2, 0.1, 10.0), GamutMapping::Chroma).get_tuple(), (255, 255, 255));
+        assert_eq!(map_to_gamut(OKLCh::new(-0.1, 0.1, 10.0), GamutMapping::MINDE).get_tuple(), (0, 0, 0));
+        assert_eq!(map_to_gamut(OKLCh::from(RGB::new(30, 144, 255)), GamutMapping::Chroma).get_tuple(), (30, 144, 255));
+    }
+}
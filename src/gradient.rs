@@ -0,0 +1,382 @@
+use crate::*;
+
+// Precision of the cubic bezier solver
+const BEZIER_EPSILON: f64 = 0.000001;
+
+/// The easing of a gradient segment.
+///
+/// It changes how fast the color goes
+/// from one stop to the next one.
+///
+/// CubicBezier takes the two control points
+/// (x1, y1, x2, y2) like the CSS cubic-bezier()
+/// function, the x values are clamped to 0 to 1.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum Easing
+{
+    #[default]
+    Linear,
+    SmoothStep,
+    CubicBezier(f64, f64, f64, f64)
+}
+
+impl Easing
+{
+    /// Applies the easing to a value from 0 to 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Easing;
+    /// assert_eq!(Easing::SmoothStep.apply(0.25), 0.15625);
+    /// ```
+    pub fn apply(&self, x: f64) -> f64
+    {
+        let x = x.clamp(0.0, 1.0);
+
+        match *self
+        {
+            Easing::Linear => x,
+            Easing::SmoothStep => x * x * (3.0 - 2.0 * x),
+            Easing::CubicBezier(x1, y1, x2, y2) =>
+            {
+                let u = solve_bezier(x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0), x);
+                bezier(y1, y2, u)
+            }
+        }
+    }
+}
+
+/// A gradient made of color stops.
+///
+/// Each stop has a position, usually from 0 to 1,
+/// a color and the easing of the segment that
+/// starts at it.
+///
+/// Colors between stops are mixed in a
+/// color space, see the mix definition.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{Gradient, ColorSpace, RGB};
+/// let mut g = Gradient::new(ColorSpace::OKLab);
+/// g.add_stop(0.0, RGB::new(0, 0, 255));
+/// g.add_stop(1.0, RGB::new(255, 0, 0));
+/// let colors = g.take(5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient
+{
+    stops: Vec<(f64, RGB, Easing)>,
+    space: ColorSpace,
+    hue: HueInterpolation
+}
+
+impl Gradient
+{
+    /// Makes a new gradient without stops
+    /// that mixes colors in a color space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace};
+    /// let g = Gradient::new(ColorSpace::OKLCh);
+    /// ```
+    pub fn new(space: ColorSpace) -> Gradient
+    {
+        Gradient
+        {
+            stops: Vec::new(), space, hue: HueInterpolation::Shorter
+        }
+    }
+
+    /// Makes a new gradient with the colors
+    /// evenly spaced from 0 to 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace, RGB};
+    /// let colors = [RGB::new(0, 0, 0), RGB::new(255, 0, 0), RGB::new(255, 255, 0)];
+    /// let g = Gradient::from_colors(&colors, ColorSpace::OKLab);
+    /// ```
+    pub fn from_colors(colors: &[RGB], space: ColorSpace) -> Gradient
+    {
+        let mut g = Gradient::new(space);
+        let last = colors.len().saturating_sub(1).max(1) as f64;
+
+        for (i, c) in colors.iter().enumerate()
+        {
+            g.add_stop(i as f64 / last, *c);
+        }
+
+        g
+    }
+
+    /// Adds a stop with a linear segment.
+    ///
+    /// Stops are kept sorted by position,
+    /// a stop at the same position as another one
+    /// goes after it and makes a hard edge.
+    ///
+    /// Positions that are NaN or infinite are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace, RGB};
+    /// let mut g = Gradient::new(ColorSpace::OKLab);
+    /// g.add_stop(0.5, RGB::new(30, 144, 255));
+    /// ```
+    pub fn add_stop(&mut self, position: f64, color: RGB)
+    {
+        self.add_stop_with(position, color, Easing::Linear);
+    }
+
+    /// Adds a stop with the easing used
+    /// for the segment that starts at it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace, Easing, RGB};
+    /// let mut g = Gradient::new(ColorSpace::OKLab);
+    /// g.add_stop_with(0.0, RGB::new(0, 0, 0), Easing::SmoothStep);
+    /// g.add_stop(1.0, RGB::new(255, 255, 255));
+    /// ```
+    pub fn add_stop_with(&mut self, position: f64, color: RGB, easing: Easing)
+    {
+        if !position.is_finite() {return}
+        let i = self.stops.iter().take_while(|s| s.0 <= position).count();
+        self.stops.insert(i, (position, color, easing));
+    }
+
+    /// Gets the stops as positions and colors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace, RGB};
+    /// let g = Gradient::from_colors(&[RGB::new(0, 0, 0), RGB::new(255, 0, 0)], ColorSpace::SRGB);
+    /// let stops = g.get_stops();
+    /// ```
+    pub fn get_stops(&self) -> Vec<(f64, RGB)>
+    {
+        self.stops.iter().map(|s| (s.0, s.1)).collect()
+    }
+
+    /// Gets the color space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace};
+    /// let space = Gradient::new(ColorSpace::OKLab).get_space();
+    /// ```
+    pub fn get_space(&self) -> ColorSpace
+    {
+        self.space
+    }
+
+    /// Sets the color space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace};
+    /// let mut g = Gradient::new(ColorSpace::OKLab);
+    /// g.set_space(ColorSpace::LinearSRGB);
+    /// ```
+    pub fn set_space(&mut self, space: ColorSpace)
+    {
+        self.space = space;
+    }
+
+    /// Gets the hue interpolation method.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace};
+    /// let hue = Gradient::new(ColorSpace::OKLCh).get_hue_interpolation();
+    /// ```
+    pub fn get_hue_interpolation(&self) -> HueInterpolation
+    {
+        self.hue
+    }
+
+    /// Sets the hue interpolation method
+    /// used by the polar color spaces.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace, HueInterpolation};
+    /// let mut g = Gradient::new(ColorSpace::OKLCh);
+    /// g.set_hue_interpolation(HueInterpolation::Longer);
+    /// ```
+    pub fn set_hue_interpolation(&mut self, hue: HueInterpolation)
+    {
+        self.hue = hue;
+    }
+
+    /// Gets the color at a position.
+    ///
+    /// Positions before the first stop or after
+    /// the last one get the color of that stop.
+    ///
+    /// A gradient without stops is black.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace, RGB};
+    /// let g = Gradient::from_colors(&[RGB::new(0, 0, 0), RGB::new(255, 0, 0)], ColorSpace::SRGB);
+    /// assert_eq!(g.sample(0.5), RGB::new(128, 0, 0));
+    /// ```
+    pub fn sample(&self, t: f64) -> RGB
+    {
+        let (first, last) = match (self.stops.first(), self.stops.last())
+        {
+            (Some(first), Some(last)) => (first, last),
+            _ => return RGB::new(0, 0, 0)
+        };
+
+        if t.is_nan() || t <= first.0 {return first.1}
+        if t >= last.0 {return last.1}
+
+        // The last segment that starts at or before t
+        let i = self.stops.iter().rposition(|s| s.0 <= t).unwrap();
+        let (a, b) = (&self.stops[i], &self.stops[i + 1]);
+        let x = a.2.apply((t - a.0) / (b.0 - a.0));
+        RGB::from_tuple(mix_with(a.1.get_tuple(), b.1.get_tuple(), x, self.space, self.hue))
+    }
+
+    /// Gets a number of colors evenly spaced
+    /// from the first stop to the last one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Gradient, ColorSpace, RGB};
+    /// let g = Gradient::from_colors(&[RGB::new(0, 0, 0), RGB::new(255, 0, 0)], ColorSpace::SRGB);
+    /// let colors = g.take(10);
+    /// ```
+    pub fn take(&self, n: usize) -> Vec<RGB>
+    {
+        let (start, end) = match (self.stops.first(), self.stops.last())
+        {
+            (Some(first), Some(last)) => (first.0, last.0),
+            _ => (0.0, 1.0)
+        };
+
+        let steps = n.saturating_sub(1).max(1) as f64;
+        (0..n).map(|i| self.sample(start + (end - start) * i as f64 / steps)).collect()
+    }
+}
+
+// Gets a coordinate of a cubic bezier
+// from (0, 0) to (1, 1) at a parameter
+fn bezier(p1: f64, p2: f64, u: f64) -> f64
+{
+    let v = 1.0 - u;
+    3.0 * v * v * u * p1 + 3.0 * v * u * u * p2 + u * u * u
+}
+
+// Finds the bezier parameter for an x value
+fn solve_bezier(x1: f64, x2: f64, x: f64) -> f64
+{
+    let (mut min, mut max) = (0.0, 1.0);
+
+    // The x coordinate always grows with the
+    // parameter when x1 and x2 are from 0 to 1
+    while max - min > BEZIER_EPSILON
+    {
+        let u = (min + max) / 2.0;
+        if bezier(x1, x2, u) < x {min = u} else {max = u}
+    }
+
+    (min + max) / 2.0
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn easing_test()
+    {
+        assert_eq!(Easing::Linear.apply(0.3), 0.3);
+        assert_eq!(Easing::Linear.apply(1.5), 1.0);
+        assert_eq!(Easing::SmoothStep.apply(0.5), 0.5);
+
+        // The CSS "ease" curve
+        let ease = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+        assert_eq!(round_float(ease.apply(0.5)), 0.8);
+        assert!(ease.apply(0.0).abs() < 0.001);
+        assert!((ease.apply(1.0) - 1.0).abs() < 0.001);
+
+        // A linear bezier
+        let linear = Easing::CubicBezier(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(round_float(linear.apply(0.37)), 0.37);
+    }
+
+    #[test]
+    fn gradient_test()
+    {
+        let (black, red, white) = (RGB::new(0, 0, 0), RGB::new(255, 0, 0), RGB::new(255, 255, 255));
+        let g = Gradient::from_colors(&[black, red, white], ColorSpace::SRGB);
+        assert_eq!(g.get_stops(), vec![(0.0, black), (0.5, red), (1.0, white)]);
+
+        assert_eq!(g.sample(-1.0), black);
+        assert_eq!(g.sample(0.25), RGB::new(128, 0, 0));
+        assert_eq!(g.sample(0.5), red);
+        assert_eq!(g.sample(0.75), RGB::new(255, 128, 128));
+        assert_eq!(g.sample(2.0), white);
+
+        assert_eq!(g.take(3), vec![black, red, white]);
+        assert_eq!(g.take(1), vec![black]);
+        assert!(g.take(0).is_empty());
+        assert_eq!(Gradient::new(ColorSpace::OKLab).sample(0.5), black);
+        assert_eq!(Gradient::from_colors(&[red], ColorSpace::OKLab).take(2), vec![red, red]);
+    }
+
+    #[test]
+    fn stop_test()
+    {
+        let mut g = Gradient::new(ColorSpace::SRGB);
+        g.add_stop(1.0, RGB::new(0, 0, 255));
+        g.add_stop(0.0, RGB::new(255, 0, 0));
+
+        // A hard edge in the middle
+        g.add_stop(0.5, RGB::new(0, 255, 0));
+        g.add_stop(0.5, RGB::new(0, 0, 0));
+        assert_eq!(g.sample(0.4999), RGB::new(0, 255, 0));
+        assert_eq!(g.sample(0.5), RGB::new(0, 0, 0));
+
+        // Stops that are not finite are ignored
+        let mut g = Gradient::new(ColorSpace::SRGB);
+        g.add_stop(f64::NAN, RGB::new(255, 0, 0));
+        g.add_stop_with(f64::INFINITY, RGB::new(0, 255, 0), Easing::SmoothStep);
+        g.add_stop(1.0, RGB::new(0, 0, 255));
+        assert_eq!(g.get_stops().len(), 1);
+        assert_eq!(g.sample(0.5), RGB::new(0, 0, 255));
+
+        let mut g = Gradient::new(ColorSpace::SRGB);
+        g.add_stop_with(0.0, RGB::new(0, 0, 0), Easing::SmoothStep);
+        g.add_stop(1.0, RGB::new(200, 200, 200));
+        assert_eq!(g.sample(0.25), RGB::new(31, 31, 31));
+
+        let mut g = Gradient::new(ColorSpace::HSL);
+        g.add_stop(0.0, RGB::new(255, 0, 0));
+        g.add_stop(1.0, RGB::new(0, 0, 255));
+        assert_eq!(g.sample(0.5), RGB::new(255, 0, 255));
+        g.set_hue_interpolation(HueInterpolation::Longer);
+        assert_eq!(g.sample(0.5), RGB::new(0, 255, 0));
+    }
+}
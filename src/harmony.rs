@@ -0,0 +1,222 @@
+use crate::*;
+
+/// A color harmony scheme.
+///
+/// Complementary adds the opposite hue.
+///
+/// SplitComplementary adds the two hues
+/// 30 degrees away from the opposite one.
+///
+/// Analogous adds hues at steps of an angle
+/// around the base color, up to a total count.
+///
+/// Triadic adds hues 120 degrees apart, Tetradic
+/// makes a rectangle with hues 60, 180 and 240
+/// degrees away, and Square adds hues 90 degrees apart.
+///
+/// Monochromatic keeps the hue and adds
+/// darker and lighter shades around the
+/// base lightness, up to a total count.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scheme
+{
+    Complementary,
+    SplitComplementary,
+    Analogous(f64, usize),
+    Triadic,
+    Tetradic,
+    Square,
+    Monochromatic(usize)
+}
+
+/// Makes a color harmony from a base color
+/// rotating hues in HSL.
+///
+/// The base color always comes first.
+///
+/// Analogous colors alternate sides, so
+/// Analogous(30.0, 5) gives hue offsets of
+/// 0, 30, -30, 60 and -60 degrees.
+///
+/// Monochromatic shades are sorted
+/// from dark to light after the base color.
+/// They split the darker and lighter sides
+/// by how much room each one has, and are
+/// evenly spaced without reaching black or white.
+///
+/// A count of 0 gives only the base color, like 1.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{color_harmony, Scheme};
+/// let colors = color_harmony((255, 0, 0), Scheme::Triadic);
+/// assert_eq!(colors, vec![(255, 0, 0), (0, 255, 0), (0, 0, 255)]);
+/// ```
+pub fn color_harmony(t: (u8, u8, u8), scheme: Scheme) -> Vec<(u8, u8, u8)>
+{
+    color_harmony_with(t, scheme, LightnessModel::HSL)
+}
+
+/// Makes a color harmony from a base color
+/// using a lightness model.
+///
+/// With OKLCh the hues are rotated keeping the
+/// perceived lightness, and the chroma is lowered
+/// if a color falls outside of the sRGB gamut.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{color_harmony_with, Scheme, LightnessModel};
+/// let colors = color_harmony_with((30, 144, 255), Scheme::Analogous(30.0, 3), LightnessModel::OKLCh);
+/// ```
+pub fn color_harmony_with(t: (u8, u8, u8), scheme: Scheme, model: LightnessModel) -> Vec<(u8, u8, u8)>
+{
+    let offsets = match scheme
+    {
+        Scheme::Complementary => vec![0.0, 180.0],
+        Scheme::SplitComplementary => vec![0.0, 150.0, 210.0],
+        Scheme::Triadic => vec![0.0, 120.0, 240.0],
+        Scheme::Tetradic => vec![0.0, 60.0, 180.0, 240.0],
+        Scheme::Square => vec![0.0, 90.0, 180.0, 270.0],
+        Scheme::Analogous(angle, count) =>
+        {
+            (0..count.max(1)).map(|i|
+            {
+                let step = (i - i / 2) as f64 * angle;
+                if i % 2 == 1 {step} else {-step}
+            }).collect()
+        },
+        Scheme::Monochromatic(count) =>
+        {
+            let mut colors = vec![t];
+            let lightness = get_lightness(t, model);
+            let others = count.saturating_sub(1);
+            let darker = (others as f64 * lightness).round() as usize;
+            let lighter = others - darker;
+
+            for i in 1..=darker
+            {
+                colors.push(with_lightness(t, lightness * i as f64 / (darker + 1) as f64, model));
+            }
+
+            for i in 1..=lighter
+            {
+                colors.push(with_lightness(t, lightness + (1.0 - lightness) * i as f64 / (lighter + 1) as f64, model));
+            }
+
+            return colors
+        }
+    };
+
+    offsets.into_iter().map(|offset| if offset == 0.0 {t} else {with_hue_offset(t, offset, model)}).collect()
+}
+
+// Rotates the hue of a color by some degrees
+fn with_hue_offset(t: (u8, u8, u8), degrees: f64, model: LightnessModel) -> (u8, u8, u8)
+{
+    match model
+    {
+        LightnessModel::HSL =>
+        {
+            let hsl = get_hsl(t);
+            unit_rgb_to_tuple(hsl_to_unit_rgb(hsl.get_hue() + degrees,
+                hsl.get_saturation() / 100.0, hsl.get_lightness() / 100.0))
+        },
+        LightnessModel::OKLCh =>
+        {
+            let (l, c, h) = OKLCh::from(RGB::from_tuple(t)).get_tuple();
+            map_to_gamut(OKLCh::new(l, c, h + degrees), GamutMapping::Chroma).get_tuple()
+        }
+    }
+}
+
+// Gets the lightness of a color
+// as a value from 0 to 1
+fn get_lightness(t: (u8, u8, u8), model: LightnessModel) -> f64
+{
+    match model
+    {
+        LightnessModel::HSL => get_hsl(t).get_lightness() / 100.0,
+        LightnessModel::OKLCh => OKLCh::from(RGB::from_tuple(t)).get_l().clamp(0.0, 1.0)
+    }
+}
+
+// Changes the lightness of a color
+// with a value from 0 to 1
+fn with_lightness(t: (u8, u8, u8), lightness: f64, model: LightnessModel) -> (u8, u8, u8)
+{
+    match model
+    {
+        LightnessModel::HSL =>
+        {
+            let hsl = get_hsl(t);
+            unit_rgb_to_tuple(hsl_to_unit_rgb(hsl.get_hue(), hsl.get_saturation() / 100.0, lightness))
+        },
+        LightnessModel::OKLCh =>
+        {
+            let (_, c, h) = OKLCh::from(RGB::from_tuple(t)).get_tuple();
+            map_to_gamut(OKLCh::new(lightness, c, h), GamutMapping::Chroma).get_tuple()
+        }
+    }
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn harmony_test()
+    {
+        let red = (255, 0, 0);
+        assert_eq!(color_harmony(red, Scheme::Complementary), vec![red, (0, 255, 255)]);
+        assert_eq!(color_harmony(red, Scheme::SplitComplementary), vec![red, (0, 255, 128), (0, 128, 255)]);
+        assert_eq!(color_harmony(red, Scheme::Tetradic), vec![red, (255, 255, 0), (0, 255, 255), (0, 0, 255)]);
+        assert_eq!(color_harmony(red, Scheme::Square), vec![red, (128, 255, 0), (0, 255, 255), (128, 0, 255)]);
+        assert_eq!(color_harmony(red, Scheme::Analogous(30.0, 3)), vec![red, (255, 128, 0), (255, 0, 128)]);
+        assert_eq!(color_harmony(red, Scheme::Analogous(30.0, 0)), vec![red]);
+        assert_eq!(color_harmony(red, Scheme::Analogous(30.0, 1)), vec![red]);
+
+        // Two darker shades and one lighter around
+        // the 50% lightness, without repeating red
+        let shades = color_harmony(red, Scheme::Monochromatic(4));
+        assert_eq!(shades, vec![red, (85, 0, 0), (170, 0, 0), (255, 128, 128)]);
+        assert_eq!(color_harmony(red, Scheme::Monochromatic(1)), vec![red]);
+        assert_eq!(color_harmony(red, Scheme::Monochromatic(0)), vec![red]);
+
+        // A dark base gets mostly lighter shades
+        let navy = (0, 0, 128);
+        let shades = color_harmony(navy, Scheme::Monochromatic(5));
+        assert_eq!(shades.len(), 5);
+        assert!(shades[1..].iter().all(|c| *c != navy));
+        assert_eq!(shades.iter().filter(|c| get_color_lightness(**c) > get_color_lightness(navy)).count(), 3);
+    }
+
+    #[test]
+    fn harmony_model_test()
+    {
+        let base = (30, 144, 255);
+        let colors = color_harmony_with(base, Scheme::Triadic, LightnessModel::OKLCh);
+        assert_eq!(colors.len(), 3);
+        assert_eq!(colors[0], base);
+
+        // OKLCh keeps the lightness and the hues
+        // are close to 120 degrees apart
+        let lch: Vec<OKLCh> = colors.iter().map(|c| OKLCh::from(RGB::from_tuple(*c))).collect();
+
+        for c in lch.iter().skip(1)
+        {
+            assert!((c.get_l() - lch[0].get_l()).abs() < 0.01);
+        }
+
+        assert!(((lch[1].get_h() - lch[0].get_h()).rem_euclid(360.0) - 120.0).abs() < 2.0);
+        assert!(((lch[2].get_h() - lch[0].get_h()).rem_euclid(360.0) - 240.0).abs() < 2.0);
+
+        let shades = color_harmony_with(base, Scheme::Monochromatic(3), LightnessModel::OKLCh);
+        assert!(OKLCh::from(RGB::from_tuple(shades[1])).get_l() < OKLCh::from(RGB::from_tuple(shades[2])).get_l());
+    }
+}
@@ -0,0 +1,404 @@
+use crate::*;
+
+// CIE constants for the Lab transfer function
+const EPSILON: f64 = 216.0 / 24389.0;
+const KAPPA: f64 = 24389.0 / 27.0;
+
+/// A color in the CIELAB color space.
+///
+/// L goes from 0 to 100 and a and b
+/// are usually between -128 and 127.
+///
+/// Values are relative to a reference white.
+///
+/// Conversions from RGB give D65 values,
+/// use to_white to get the D50 values
+/// used by the CSS lab() function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lab
+{
+    l: f64,
+    a: f64,
+    b: f64,
+    white: WhitePoint
+}
+
+impl Lab
+{
+    /// Makes a new D65 Lab from three values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Lab;
+    /// let c = Lab::new(53.24, 80.09, 67.2);
+    /// ```
+    pub fn new(l: f64, a: f64, b: f64) -> Lab
+    {
+        Lab::with_white(l, a, b, WhitePoint::D65)
+    }
+
+    /// Makes a new Lab from three values
+    /// relative to a reference white.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Lab, WhitePoint};
+    /// let c = Lab::with_white(54.29, 80.81, 69.89, WhitePoint::D50);
+    /// ```
+    pub fn with_white(l: f64, a: f64, b: f64, white: WhitePoint) -> Lab
+    {
+        Lab
+        {
+            l, a, b, white
+        }
+    }
+
+    /// Gets the L value (lightness).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Lab;
+    /// let l = Lab::new(53.24, 80.09, 67.2).get_l();
+    /// ```
+    pub fn get_l(&self) -> f64
+    {
+        self.l
+    }
+
+    /// Gets the a value (green to red).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Lab;
+    /// let a = Lab::new(53.24, 80.09, 67.2).get_a();
+    /// ```
+    pub fn get_a(&self) -> f64
+    {
+        self.a
+    }
+
+    /// Gets the b value (blue to yellow).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Lab;
+    /// let b = Lab::new(53.24, 80.09, 67.2).get_b();
+    /// ```
+    pub fn get_b(&self) -> f64
+    {
+        self.b
+    }
+
+    /// Gets the reference white.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Lab;
+    /// let white = Lab::new(53.24, 80.09, 67.2).get_white();
+    /// ```
+    pub fn get_white(&self) -> WhitePoint
+    {
+        self.white
+    }
+
+    /// Gets the Lab values in a tuple.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Lab;
+    /// let t = Lab::new(53.24, 80.09, 67.2).get_tuple();
+    /// ```
+    pub fn get_tuple(&self) -> (f64, f64, f64)
+    {
+        (self.l, self.a, self.b)
+    }
+
+    /// Converts the Lab to another reference white
+    /// using the Bradford transform.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Lab, WhitePoint};
+    /// let d50 = Lab::new(53.24, 80.09, 67.2).to_white(WhitePoint::D50);
+    /// ```
+    pub fn to_white(&self, white: WhitePoint) -> Lab
+    {
+        if white == self.white {return *self}
+        Lab::from(XYZ::from(*self).to_white(white))
+    }
+}
+
+impl From<XYZ> for Lab
+{
+    fn from(c: XYZ) -> Lab
+    {
+        let w = c.get_white().get_tuple();
+
+        let f = |n: f64|
+        {
+            if n > EPSILON {n.cbrt()} else {(KAPPA * n + 16.0) / 116.0}
+        };
+
+        let fx = f(c.get_x() / w.0);
+        let fy = f(c.get_y() / w.1);
+        let fz = f(c.get_z() / w.2);
+
+        Lab::with_white(116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz), c.get_white())
+    }
+}
+
+impl From<Lab> for XYZ
+{
+    fn from(c: Lab) -> XYZ
+    {
+        let w = c.white.get_tuple();
+        let fy = (c.l + 16.0) / 116.0;
+        let fx = fy + c.a / 500.0;
+        let fz = fy - c.b / 200.0;
+
+        let f = |n: f64|
+        {
+            if n.powi(3) > EPSILON {n.powi(3)} else {(116.0 * n - 16.0) / KAPPA}
+        };
+
+        let y = if c.l > KAPPA * EPSILON {fy.powi(3)} else {c.l / KAPPA};
+        XYZ::with_white(f(fx) * w.0, y * w.1, f(fz) * w.2, c.white)
+    }
+}
+
+impl From<RGB> for Lab
+{
+    fn from(c: RGB) -> Lab
+    {
+        Lab::from(XYZ::from(c))
+    }
+}
+
+impl From<Lab> for RGB
+{
+    fn from(c: Lab) -> RGB
+    {
+        RGB::from(XYZ::from(c))
+    }
+}
+
+/// A color in the cylindrical LCh(ab) form of CIELAB.
+///
+/// L goes from 0 to 100, C is the chroma
+/// and h is the hue in degrees.
+///
+/// The reference white works like in Lab.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LCh
+{
+    l: f64,
+    c: f64,
+    h: f64,
+    white: WhitePoint
+}
+
+impl LCh
+{
+    /// Makes a new D65 LCh from three values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::LCh;
+    /// let c = LCh::new(53.24, 104.55, 40.0);
+    /// ```
+    pub fn new(l: f64, c: f64, h: f64) -> LCh
+    {
+        LCh::with_white(l, c, h, WhitePoint::D65)
+    }
+
+    /// Makes a new LCh from three values
+    /// relative to a reference white.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{LCh, WhitePoint};
+    /// let c = LCh::with_white(54.29, 106.84, 40.85, WhitePoint::D50);
+    /// ```
+    pub fn with_white(l: f64, c: f64, h: f64, white: WhitePoint) -> LCh
+    {
+        LCh
+        {
+            l, c, h: h.rem_euclid(360.0), white
+        }
+    }
+
+    /// Gets the L value (lightness).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::LCh;
+    /// let l = LCh::new(53.24, 104.55, 40.0).get_l();
+    /// ```
+    pub fn get_l(&self) -> f64
+    {
+        self.l
+    }
+
+    /// Gets the C value (chroma).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::LCh;
+    /// let c = LCh::new(53.24, 104.55, 40.0).get_c();
+    /// ```
+    pub fn get_c(&self) -> f64
+    {
+        self.c
+    }
+
+    /// Gets the h value (hue degrees).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::LCh;
+    /// let h = LCh::new(53.24, 104.55, 40.0).get_h();
+    /// ```
+    pub fn get_h(&self) -> f64
+    {
+        self.h
+    }
+
+    /// Gets the reference white.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::LCh;
+    /// let white = LCh::new(53.24, 104.55, 40.0).get_white();
+    /// ```
+    pub fn get_white(&self) -> WhitePoint
+    {
+        self.white
+    }
+
+    /// Gets the LCh values in a tuple.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::LCh;
+    /// let t = LCh::new(53.24, 104.55, 40.0).get_tuple();
+    /// ```
+    pub fn get_tuple(&self) -> (f64, f64, f64)
+    {
+        (self.l, self.c, self.h)
+    }
+
+    /// Converts the LCh to another reference white
+    /// using the Bradford transform.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{LCh, WhitePoint};
+    /// let d50 = LCh::new(53.24, 104.55, 40.0).to_white(WhitePoint::D50);
+    /// ```
+    pub fn to_white(&self, white: WhitePoint) -> LCh
+    {
+        if white == self.white {return *self}
+        LCh::from(Lab::from(*self).to_white(white))
+    }
+}
+
+impl From<Lab> for LCh
+{
+    fn from(c: Lab) -> LCh
+    {
+        let (l, a, b) = c.get_tuple();
+        LCh::with_white(l, a.hypot(b), b.atan2(a).to_degrees(), c.get_white())
+    }
+}
+
+impl From<LCh> for Lab
+{
+    fn from(c: LCh) -> Lab
+    {
+        let h = c.h.to_radians();
+        Lab::with_white(c.l, c.c * h.cos(), c.c * h.sin(), c.white)
+    }
+}
+
+impl From<RGB> for LCh
+{
+    fn from(c: RGB) -> LCh
+    {
+        LCh::from(Lab::from(c))
+    }
+}
+
+impl From<LCh> for RGB
+{
+    fn from(c: LCh) -> RGB
+    {
+        RGB::from(Lab::from(c))
+    }
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn round2(t: (f64, f64, f64)) -> (f64, f64, f64)
+    {
+        (round_float(t.0), round_float(t.1), round_float(t.2))
+    }
+
+    #[test]
+    fn lab_test()
+    {
+        // Published values for sRGB red
+        let red = Lab::from(RGB::new(255, 0, 0));
+        assert_eq!(round2(red.get_tuple()), (53.24, 80.09, 67.2));
+        assert_eq!(round2(red.to_white(WhitePoint::D50).get_tuple()), (54.29, 80.8, 69.89));
+
+        let white = Lab::from(RGB::new(255, 255, 255));
+        assert_eq!(round2(white.get_tuple()), (100.0, 0.0, 0.0));
+        assert_eq!(round2(white.to_white(WhitePoint::D50).get_tuple()), (100.0, 0.0, 0.0));
+
+        let c = Lab::with_white(54.29, 80.8, 69.89, WhitePoint::D50);
+        assert_eq!(RGB::from(c).get_tuple(), (255, 0, 0));
+
+        for t in [(255, 0, 0), (30, 144, 255), (12, 200, 99), (0, 0, 0), (1, 1, 1)].iter()
+        {
+            let c = RGB::from_tuple(*t);
+            assert_eq!(RGB::from(Lab::from(c)), c);
+            assert_eq!(RGB::from(Lab::from(c).to_white(WhitePoint::D50)), c);
+        }
+    }
+
+    #[test]
+    fn lch_test()
+    {
+        let red = LCh::from(RGB::new(255, 0, 0));
+        assert_eq!(round2(red.to_white(WhitePoint::D50).get_tuple()), (54.29, 106.84, 40.86));
+        assert_eq!(round2(LCh::from(RGB::new(0, 0, 255)).get_tuple()), (32.3, 133.81, 306.29));
+        assert_eq!(LCh::new(50.0, 10.0, -30.0).get_h(), 330.0);
+
+        for t in [(255, 0, 0), (30, 144, 255), (12, 200, 99), (128, 128, 128)].iter()
+        {
+            let c = RGB::from_tuple(*t);
+            assert_eq!(RGB::from(LCh::from(c)), c);
+        }
+    }
+}
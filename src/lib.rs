@@ -1,16 +1,87 @@
 mod rgb;
+mod rgba;
+mod xyz;
+mod lab;
+mod oklab;
+mod gamut;
+mod blend;
+mod mix;
+mod gradient;
+mod colormap;
+mod scale;
+mod harmony;
+mod contrast;
+mod apca;
+mod delta_e;
+mod palette;
+mod error;
+mod parser;
+mod names;
 mod utils;
 
 pub use rgb::RGB;
+pub use rgba::RGBA;
+pub use xyz::{XYZ, WhitePoint, rgb_to_linear, linear_to_rgb};
+pub use lab::{Lab, LCh};
+pub use oklab::{OKLab, OKLCh};
+pub use gamut::{GamutMapping, in_gamut, map_to_gamut};
+pub use blend::{BlendMode, blend, blend_rgba};
+pub use mix::{ColorSpace, HueInterpolation, mix, mix_with};
+pub use gradient::{Gradient, Easing};
+pub use colormap::Colormap;
+pub use scale::{Scale, ScaleTransform};
+pub use harmony::{Scheme, color_harmony, color_harmony_with};
+pub use contrast::{ContrastLevel, relative_luminance, contrast_ratio, meets_contrast, contrast_levels,
+    best_text_color, ensure_contrast};
+pub use apca::{Polarity, apca_contrast, apca_min_font_size, apca_min_contrast};
+pub use delta_e::{Metric, delta_e, delta_e_rgb};
+pub use palette::Palette;
+pub use error::ColorError;
+pub use parser::{try_parse_color, try_parse_color_with, try_parse_rgba, try_parse_rgba_with, ParseOptions};
 use utils::*;
+use names::*;
 
-/// These are the percentages used to
-/// make colors darker or lighter
-const PERCENT_1: f64 = 10.0;
-const PERCENT_2: f64 = 20.0;
-const PERCENT_3: f64 = 30.0;
-const PERCENT_4: f64 = 40.0;
-const PERCENT_5: f64 = 50.0;
+/// The color space used to make
+/// colors darker or lighter
+/// and to rotate hues in harmonies.
+/// 
+/// HSL is the default and matches
+/// the original behaviour of the crate.
+/// 
+/// OKLCh is perceptually uniform,
+/// so steps look even and the hue
+/// doesn't shift on blues and yellows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LightnessModel
+{
+    #[default]
+    HSL,
+    OKLCh
+}
+
+/// Gets an RGB tuple from a color name.
+/// 
+/// The input is lowercased and the whitespaces are removed.
+/// 
+/// So "Light Blue" will match "lightblue".
+/// 
+/// Returns None if the name doesn't exist.
+/// 
+/// The names are the CSS Color Module Level 4 list,
+/// plus "transparent" which returns black.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::lookup_color_name;
+/// let c = lookup_color_name("rebeccapurple");
+/// assert_eq!(c, Some((102, 51, 153)));
+/// ```
+pub fn lookup_color_name(name: &str) -> Option<(u8, u8, u8)>
+{
+    let cname = clean_string(name);
+    COLOR_NAMES.iter().find(|(n, _)| *n == cname).map(|(_, rgb)| *rgb)
+}
 
 /// Gets an RGB tuple from a color name.
 /// 
@@ -28,156 +99,7 @@ const PERCENT_5: f64 = 50.0;
 /// ```
 pub fn color_name_to_rgb(name: &str, fallback: (u8, u8, u8)) -> (u8, u8, u8)
 {
-    let cname = clean_string(name);
-
-    match &cname[..]
-    {
-        "maroon" => (128,0,0),
-        "darkred" => (139,0,0),
-        "brown" => (165,42,42),
-        "firebrick" => (178,34,34),
-        "crimson" => (220,20,60),
-        "red" => (255,0,0),
-        "tomato" => (255,99,71),
-        "coral" => (255,127,80),
-        "indianred" => (205,92,92),
-        "lightcoral" => (240,128,128),
-        "darksalmon" => (233,150,122),
-        "salmon" => (250,128,114),
-        "lightsalmon" => (255,160,122),
-        "orangered" => (255,69,0),
-        "darkorange" => (255,140,0),
-        "orange" => (255,165,0),
-        "gold" => (255,215,0),
-        "darkgoldenrod" => (184,134,11),
-        "goldenrod" => (218,165,32),
-        "palegoldenrod" => (238,232,170),
-        "darkkhaki" => (189,183,107),
-        "khaki" => (240,230,140),
-        "olive" => (128,128,0),
-        "yellow" => (255,255,0),
-        "yellowgreen" => (154,205,50),
-        "darkolivegreen" => (85,107,47),
-        "olivedrab" => (107,142,35),
-        "lawngreen" => (124,252,0),
-        "chartreuse" => (127,255,0),
-        "greenyellow" => (173,255,47),
-        "darkgreen" => (0,100,0),
-        "green" => (0,128,0),
-        "forestgreen" => (34,139,34),
-        "lime" => (0,255,0),
-        "limegreen" => (50,205,50),
-        "lightgreen" => (144,238,144),
-        "palegreen" => (152,251,152),
-        "darkseagreen" => (143,188,143),
-        "mediumspringgreen" => (0,250,154),
-        "springgreen" => (0,255,127),
-        "seagreen" => (46,139,87),
-        "mediumaquamarine" => (102,205,170),
-        "mediumseagreen" => (60,179,113),
-        "lightseagreen" => (32,178,170),
-        "darkslategray" => (47,79,79),
-        "teal" => (0,128,128),
-        "darkcyan" => (0,139,139),
-        "aqua" => (0,255,255),
-        "cyan" => (0,255,255),
-        "lightcyan" => (224,255,255),
-        "darkturquoise" => (0,206,209),
-        "turquoise" => (64,224,208),
-        "mediumturquoise" => (72,209,204),
-        "paleturquoise" => (175,238,238),
-        "aquamarine" => (127,255,212),
-        "powderblue" => (176,224,230),
-        "cadetblue" => (95,158,160),
-        "steelblue" => (70,130,180),
-        "cornflowerblue" => (100,149,237),
-        "deepskyblue" => (0,191,255),
-        "dodgerblue" => (30,144,255),
-        "lightblue" => (173,216,230),
-        "skyblue" => (135,206,235),
-        "lightskyblue" => (135,206,250),
-        "midnightblue" => (25,25,112),
-        "navy" => (0,0,128),
-        "darkblue" => (0,0,139),
-        "mediumblue" => (0,0,205),
-        "blue" => (0,0,255),
-        "royalblue" => (65,105,225),
-        "blueviolet" => (138,43,226),
-        "indigo" => (75,0,130),
-        "darkslateblue" => (72,61,139),
-        "slateblue" => (106,90,205),
-        "mediumslateblue" => (123,104,238),
-        "mediumpurple" => (147,112,219),
-        "darkmagenta" => (139,0,139),
-        "darkviolet" => (148,0,211),
-        "darkorchid" => (153,50,204),
-        "mediumorchid" => (186,85,211),
-        "purple" => (128,0,128),
-        "thistle" => (216,191,216),
-        "plum" => (221,160,221),
-        "violet" => (238,130,238),
-        "magenta" => (255,0,255),
-        "fuchsia" => (255,0,255),
-        "orchid" => (218,112,214),
-        "mediumvioletred" => (199,21,133),
-        "palevioletred" => (219,112,147),
-        "deeppink" => (255,20,147),
-        "hotpink" => (255,105,180),
-        "lightpink" => (255,182,193),
-        "pink" => (255,192,203),
-        "antiquewhite" => (250,235,215),
-        "beige" => (245,245,220),
-        "bisque" => (255,228,196),
-        "blanchedalmond" => (255,235,205),
-        "wheat" => (245,222,179),
-        "cornsilk" => (255,248,220),
-        "lemonchiffon" => (255,250,205),
-        "lightgoldenrodyellow" => (250,250,210),
-        "lightyellow" => (255,255,224),
-        "saddlebrown" => (139,69,19),
-        "sienna" => (160,82,45),
-        "chocolate" => (210,105,30),
-        "peru" => (205,133,63),
-        "sandybrown" => (244,164,96),
-        "burlywood" => (222,184,135),
-        "tan" => (210,180,140),
-        "rosybrown" => (188,143,143),
-        "moccasin" => (255,228,181),
-        "navajowhite" => (255,222,173),
-        "peachpuff" => (255,218,185),
-        "mistyrose" => (255,228,225),
-        "lavenderblush" => (255,240,245),
-        "linen" => (250,240,230),
-        "oldlace" => (253,245,230),
-        "papayawhip" => (255,239,213),
-        "seashell" => (255,245,238),
-        "mintcream" => (245,255,250),
-        "slategray" => (112,128,144),
-        "lightslategray" => (119,136,153),
-        "lightsteelblue" => (176,196,222),
-        "lavender" => (230,230,250),
-        "floralwhite" => (255,250,240),
-        "aliceblue" => (240,248,255),
-        "ghostwhite" => (248,248,255),
-        "honeydew" => (240,255,240),
-        "ivory" => (255,255,240),
-        "azure" => (240,255,255),
-        "snow" => (255,250,250),
-        "black" => (0,0,0),
-        "dimgray" => (105,105,105),
-        "dimgrey" => (105,105,105),
-        "gray" => (128,128,128),
-        "grey" => (128,128,128),
-        "darkgray" => (169,169,169),
-        "darkgrey" => (169,169,169),
-        "silver" => (192,192,192),
-        "lightgray" => (211,211,211),
-        "lightgrey" => (211,211,211),
-        "gainsboro" => (220,220,220),
-        "whitesmoke" => (245,245,245),
-        "white" => (255,255,255),
-        _ => fallback
-    }
+    lookup_color_name(name).unwrap_or(fallback)
 }
 
 /// Checks if a color name exists.
@@ -189,11 +111,115 @@ pub fn color_name_to_rgb(name: &str, fallback: (u8, u8, u8)) -> (u8, u8, u8)
 /// let exists = check_color_name("silver");
 /// ```
 pub fn check_color_name(name: &str) -> bool
+{
+    lookup_color_name(name).is_some()
+}
+
+/// Returns an iterator over all the
+/// built-in color names and their RGB tuples.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::color_names;
+/// for (name, rgb) in color_names() {}
+/// ```
+pub fn color_names() -> impl Iterator<Item = (&'static str, (u8, u8, u8))>
+{
+    COLOR_NAMES.iter().copied()
+}
+
+/// Gets the name of the built-in color
+/// that is closest to an RGB tuple.
+/// 
+/// The distance is measured in the CIELAB
+/// color space (CIE76 delta E), which is
+/// closer to human perception than RGB.
+/// 
+/// Returns the name and the distance.
+/// 
+/// A distance of 0 means an exact match.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::nearest_color_name;
+/// let (name, distance) = nearest_color_name((250, 5, 5));
+/// assert_eq!(name, "red");
+/// ```
+pub fn nearest_color_name(t: (u8, u8, u8)) -> (&'static str, f64)
+{
+    COLOR_NAMES.iter()
+        .filter(|(n, _)| *n != "transparent")
+        .map(|(n, rgb)| (*n, lab_distance(t, *rgb)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap()
+}
+
+/// Gets the name of a built-in color
+/// only if it matches the RGB tuple exactly.
+/// 
+/// If a color has several names
+/// the first one in the table is returned,
+/// for example "aqua" instead of "cyan".
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::exact_color_name;
+/// assert_eq!(exact_color_name((255, 99, 71)), Some("tomato"));
+/// assert_eq!(exact_color_name((255, 99, 72)), None);
+/// ```
+pub fn exact_color_name(t: (u8, u8, u8)) -> Option<&'static str>
+{
+    COLOR_NAMES.iter()
+        .find(|(n, rgb)| *rgb == t && *n != "transparent")
+        .map(|(n, _)| *n)
+}
+
+/// Suggests built-in color names
+/// that are similar to a name.
+/// 
+/// The input is lowercased and the whitespaces are removed.
+/// 
+/// Names that start with the input come first,
+/// then names that contain it (or make up most of it),
+/// then names that are a few edits away.
+/// 
+/// Each group is sorted by edit distance.
+/// 
+/// At most max names are returned.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::suggest_color_names;
+/// let names = suggest_color_names("cornflower", 3);
+/// assert_eq!(names[0], "cornflowerblue");
+/// ```
+pub fn suggest_color_names(name: &str, max: usize) -> Vec<&'static str>
 {
     let cname = clean_string(name);
-    if cname == "white" {return true}
-    let rgb = color_name_to_rgb(name, (255, 255, 255));
-    rgb != (255, 255, 255)
+    if cname.is_empty() {return Vec::new()}
+    let threshold = (cname.chars().count() / 3).max(2);
+
+    let mut ranked: Vec<(u8, usize, &'static str)> = COLOR_NAMES.iter()
+        .filter_map(|(n, _)|
+        {
+            let distance = edit_distance(&cname, n);
+
+            let group = if n.starts_with(&cname[..]) {0}
+                else if n.contains(&cname[..])
+                    || (cname.contains(n) && n.len() * 2 >= cname.len()) {1}
+                else if distance <= threshold {2}
+                else {return None};
+
+            Some((group, distance, *n))
+        })
+        .collect();
+
+    ranked.sort();
+    ranked.into_iter().take(max).map(|(_, _, n)| n).collect()
 }
 
 /// Turns a color darker or lighter.
@@ -211,6 +237,7 @@ pub fn check_color_name(name: &str) -> bool
 /// use colorskill::change_color_lightness;
 /// let c = change_color_lightness((43, 56, 84), true, 20.0);
 /// ```
+#[allow(clippy::suspicious_else_formatting)]
 pub fn change_color_lightness(t: (u8, u8, u8), darker: bool, amount: f64) -> (u8, u8, u8)
 {
     // Convert to HSL
@@ -236,6 +263,40 @@ pub fn change_color_lightness(t: (u8, u8, u8), darker: bool, amount: f64) -> (u8
     get_rgb_tuple_from_hsl(&hsl)
 }
 
+/// Turns a color darker or lighter
+/// using a lightness model.
+/// 
+/// With HSL this is the same as
+/// change_color_lightness.
+/// 
+/// With OKLCh the amount is a percentage
+/// of the OKLCh lightness, and the hue is kept.
+/// 
+/// The chroma is lowered if the result
+/// falls outside of the sRGB gamut.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::{change_color_lightness_with, LightnessModel};
+/// let c = change_color_lightness_with((43, 56, 84), true, 20.0, LightnessModel::OKLCh);
+/// ```
+pub fn change_color_lightness_with(t: (u8, u8, u8), darker: bool, amount: f64, model: LightnessModel) -> (u8, u8, u8)
+{
+    match model
+    {
+        LightnessModel::HSL => change_color_lightness(t, darker, amount),
+        LightnessModel::OKLCh =>
+        {
+            let lch = OKLCh::from(RGB::from_tuple(t));
+            let delta = if darker {-amount / 100.0} else {amount / 100.0};
+            let lightness = (lch.get_l() + delta).clamp(0.0, 1.0);
+            let c = OKLCh::new(lightness, lch.get_c(), lch.get_h());
+            map_to_gamut(c, GamutMapping::Chroma).get_tuple()
+        }
+    }
+}
+
 /// Wrapper function to make a color darker.
 /// 
 /// Receives a tuple and the amount to make darker.
@@ -266,6 +327,202 @@ pub fn make_color_lighter(t: (u8, u8, u8), amount: f64) -> (u8, u8, u8)
     change_color_lightness(t, false, amount)
 }
 
+/// Wrapper function to make a color darker
+/// using a lightness model.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::{make_color_darker_with, LightnessModel};
+/// let c = make_color_darker_with((43, 56, 84), 20.0, LightnessModel::OKLCh);
+/// ```
+pub fn make_color_darker_with(t: (u8, u8, u8), amount: f64, model: LightnessModel) -> (u8, u8, u8)
+{
+    change_color_lightness_with(t, true, amount, model)
+}
+
+/// Wrapper function to make a color lighter
+/// using a lightness model.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::{make_color_lighter_with, LightnessModel};
+/// let c = make_color_lighter_with((43, 56, 84), 20.0, LightnessModel::OKLCh);
+/// ```
+pub fn make_color_lighter_with(t: (u8, u8, u8), amount: f64, model: LightnessModel) -> (u8, u8, u8)
+{
+    change_color_lightness_with(t, false, amount, model)
+}
+
+/// Turns a color more or less saturated.
+/// 
+/// Receives a tuple, a boolean to choose
+/// between desaturating (true) or saturating (false),
+/// and the percentage amount to change.
+/// 
+/// The saturation is the HSL saturation,
+/// and it stays between 0 and 100.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::change_color_saturation;
+/// let c = change_color_saturation((43, 56, 84), false, 20.0);
+/// ```
+pub fn change_color_saturation(t: (u8, u8, u8), desaturate: bool, amount: f64) -> (u8, u8, u8)
+{
+    let delta = if desaturate {-amount} else {amount};
+    set_color_saturation(t, get_hsl(t).get_saturation() + delta)
+}
+
+/// Wrapper function to make a color more saturated.
+/// 
+/// Receives a tuple and the amount to saturate.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::saturate_color;
+/// let c = saturate_color((43, 56, 84), 20.0);
+/// ```
+pub fn saturate_color(t: (u8, u8, u8), amount: f64) -> (u8, u8, u8)
+{
+    change_color_saturation(t, false, amount)
+}
+
+/// Wrapper function to make a color less saturated.
+/// 
+/// Receives a tuple and the amount to desaturate.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::desaturate_color;
+/// let c = desaturate_color((43, 56, 84), 20.0);
+/// ```
+pub fn desaturate_color(t: (u8, u8, u8), amount: f64) -> (u8, u8, u8)
+{
+    change_color_saturation(t, true, amount)
+}
+
+/// Rotates the HSL hue of a color by some degrees.
+/// 
+/// Negative degrees rotate the other way.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::rotate_color_hue;
+/// let c = rotate_color_hue((255, 0, 0), 120.0);
+/// assert_eq!(c, (0, 255, 0));
+/// ```
+pub fn rotate_color_hue(t: (u8, u8, u8), degrees: f64) -> (u8, u8, u8)
+{
+    set_color_hue(t, get_hsl(t).get_hue() + degrees)
+}
+
+/// Changes the HSL hue of a color.
+/// 
+/// The hue is in degrees and wraps around,
+/// so 390 is the same as 30.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::set_color_hue;
+/// let c = set_color_hue((255, 0, 0), 240.0);
+/// assert_eq!(c, (0, 0, 255));
+/// ```
+pub fn set_color_hue(t: (u8, u8, u8), hue: f64) -> (u8, u8, u8)
+{
+    let mut hsl = get_hsl(t);
+    hsl.set_hue(hue.rem_euclid(360.0));
+    get_rgb_tuple_from_hsl(&hsl)
+}
+
+/// Changes the HSL saturation of a color.
+/// 
+/// The saturation is a percentage
+/// and it's clamped between 0 and 100.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::set_color_saturation;
+/// let c = set_color_saturation((43, 56, 84), 100.0);
+/// ```
+pub fn set_color_saturation(t: (u8, u8, u8), saturation: f64) -> (u8, u8, u8)
+{
+    let mut hsl = get_hsl(t);
+    hsl.set_saturation(saturation.clamp(0.0, 100.0));
+    get_rgb_tuple_from_hsl(&hsl)
+}
+
+/// Changes the HSL lightness of a color.
+/// 
+/// The lightness is a percentage
+/// and it's clamped between 0 and 100.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::set_color_lightness;
+/// let c = set_color_lightness((255, 0, 0), 25.0);
+/// assert_eq!(c, (128, 0, 0));
+/// ```
+pub fn set_color_lightness(t: (u8, u8, u8), lightness: f64) -> (u8, u8, u8)
+{
+    let mut hsl = get_hsl(t);
+    hsl.set_lightness(lightness.clamp(0.0, 100.0));
+    get_rgb_tuple_from_hsl(&hsl)
+}
+
+/// Turns a color into a gray
+/// with the same HSL lightness.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::grayscale_color;
+/// let c = grayscale_color((255, 0, 0));
+/// assert_eq!(c, (128, 128, 128));
+/// ```
+pub fn grayscale_color(t: (u8, u8, u8)) -> (u8, u8, u8)
+{
+    set_color_saturation(t, 0.0)
+}
+
+/// Inverts the channels of a color,
+/// so black turns white.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::invert_color;
+/// let c = invert_color((255, 128, 0));
+/// assert_eq!(c, (0, 127, 255));
+/// ```
+pub fn invert_color(t: (u8, u8, u8)) -> (u8, u8, u8)
+{
+    (255 - t.0, 255 - t.1, 255 - t.2)
+}
+
+/// Gets the complement of a color,
+/// which has the opposite HSL hue.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::complement_color;
+/// let c = complement_color((255, 0, 0));
+/// assert_eq!(c, (0, 255, 255));
+/// ```
+pub fn complement_color(t: (u8, u8, u8)) -> (u8, u8, u8)
+{
+    rotate_color_hue(t, 180.0)
+}
+
 /// Converts the RGB to a HSL
 /// and returns the hue value.
 /// 
@@ -305,6 +562,81 @@ pub fn get_color_lightness(t: (u8, u8, u8)) -> f64
     round_float(hsl.get_lightness())
 }
 
+/// Converts an RGB tuple into HSV values.
+/// 
+/// Returns the hue in degrees and the
+/// saturation and value as percentages.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::rgb_to_hsv;
+/// let hsv = rgb_to_hsv((51, 77, 102));
+/// ```
+pub fn rgb_to_hsv(t: (u8, u8, u8)) -> (f64, f64, f64)
+{
+    let (r, g, b) = (f64::from(t.0) / 255.0, f64::from(t.1) / 255.0, f64::from(t.2) / 255.0);
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+
+    let hue = if delta == 0.0 {0.0}
+        else if max == r {60.0 * ((g - b) / delta).rem_euclid(6.0)}
+        else if max == g {60.0 * ((b - r) / delta + 2.0)}
+        else {60.0 * ((r - g) / delta + 4.0)};
+
+    let saturation = if max == 0.0 {0.0} else {delta / max};
+    (hue, saturation * 100.0, max * 100.0)
+}
+
+/// Converts HSV values into an RGB tuple.
+/// 
+/// Receives the hue in degrees and the
+/// saturation and value as percentages.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::hsv_to_rgb;
+/// let c = hsv_to_rgb((210.0, 50.0, 40.0));
+/// ```
+pub fn hsv_to_rgb(hsv: (f64, f64, f64)) -> (u8, u8, u8)
+{
+    let s = hsv.1.clamp(0.0, 100.0) / 100.0;
+    let v = hsv.2.clamp(0.0, 100.0) / 100.0;
+    unit_rgb_to_tuple(hsv_to_unit_rgb(hsv.0, s, v))
+}
+
+/// Converts the RGB to a HSV
+/// and returns the saturation value.
+/// 
+/// This is not the same as the
+/// HSL saturation.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::get_color_hsv_saturation;
+/// let saturation = get_color_hsv_saturation((34, 84, 39));
+/// ```
+pub fn get_color_hsv_saturation(t: (u8, u8, u8)) -> f64
+{
+    round_float(rgb_to_hsv(t).1)
+}
+
+/// Converts the RGB to a HSV
+/// and returns the value (brightness).
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::get_color_value;
+/// let value = get_color_value((34, 84, 39));
+/// ```
+pub fn get_color_value(t: (u8, u8, u8)) -> f64
+{
+    round_float(rgb_to_hsv(t).2)
+}
+
 /// Generates a random RGB tuple.
 /// 
 /// # Example
@@ -398,6 +730,116 @@ pub fn color_to_string_4(c: (u8, u8, u8), prepend: &str) -> String
     format!("{}({}, {}, {})", prepend, c.0, c.1, c.2)
 }
 
+/// Converts an RGB tuple
+/// into a hex string.
+/// 
+/// The uppercase argument controls
+/// the case of the hex digits.
+/// 
+/// If short is true the 3 digit form
+/// is used when no information is lost.
+/// 
+/// (255, 0, 0) -> "#ff0000"
+/// (255, 0, 0) -> "#F00"
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::color_to_hex;
+/// let hs = color_to_hex((30, 144, 255), false, false);
+/// ```
+#[allow(clippy::suspicious_else_formatting)]
+pub fn color_to_hex(c: (u8, u8, u8), uppercase: bool, short: bool) -> String
+{
+    let lossless = [c.0, c.1, c.2].iter().all(|n| n >> 4 == n & 0x0f);
+
+    let s = if short && lossless
+    {
+        format!("#{:x}{:x}{:x}", c.0 & 0x0f, c.1 & 0x0f, c.2 & 0x0f)
+    }
+
+    else
+    {
+        format!("#{:02x}{:02x}{:02x}", c.0, c.1, c.2)
+    };
+
+    if uppercase {s.to_uppercase()} else {s}
+}
+
+/// Converts an RGBA tuple into a hex string
+/// with the alpha as the last two digits.
+/// 
+/// If short is true the 4 digit form
+/// is used when no information is lost.
+/// 
+/// (255, 0, 0, 136) -> "#ff000088"
+/// (255, 0, 0, 136) -> "#F008"
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::color_to_hex_alpha;
+/// let hs = color_to_hex_alpha((30, 144, 255, 128), false, false);
+/// ```
+#[allow(clippy::suspicious_else_formatting)]
+pub fn color_to_hex_alpha(c: (u8, u8, u8, u8), uppercase: bool, short: bool) -> String
+{
+    let lossless = [c.0, c.1, c.2, c.3].iter().all(|n| n >> 4 == n & 0x0f);
+
+    let s = if short && lossless
+    {
+        format!("#{:x}{:x}{:x}{:x}", c.0 & 0x0f, c.1 & 0x0f, c.2 & 0x0f, c.3 & 0x0f)
+    }
+
+    else
+    {
+        format!("#{:02x}{:02x}{:02x}{:02x}", c.0, c.1, c.2, c.3)
+    };
+
+    if uppercase {s.to_uppercase()} else {s}
+}
+
+/// Composites an RGBA tuple over an
+/// opaque RGB background.
+/// 
+/// Uses the Porter-Duff over operator
+/// on the sRGB values, like browsers do.
+/// 
+/// The result is the color users see.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::composite_over;
+/// let c = composite_over((255, 0, 0, 128), (255, 255, 255));
+/// assert_eq!(c, (255, 127, 127));
+/// ```
+pub fn composite_over(fg: (u8, u8, u8, u8), bg: (u8, u8, u8)) -> (u8, u8, u8)
+{
+    let a = f64::from(fg.3) / 255.0;
+    let f = |c: u8, b: u8| (f64::from(c) * a + f64::from(b) * (1.0 - a)) / 255.0;
+    unit_rgb_to_tuple((f(fg.0, bg.0), f(fg.1, bg.1), f(fg.2, bg.2)))
+}
+
+/// Converts an RGB tuple
+/// into a CSS like HSV string.
+/// 
+/// Values are rounded to 2 decimals.
+/// 
+/// (51, 77, 102) -> "hsv(209.41, 50%, 40%)"
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::color_to_hsv_string;
+/// let hs = color_to_hsv_string((51, 77, 102));
+/// ```
+pub fn color_to_hsv_string(c: (u8, u8, u8)) -> String
+{
+    let hsv = rgb_to_hsv(c);
+    format!("hsv({}, {}%, {}%)", round_float(hsv.0), round_float(hsv.1), round_float(hsv.2))
+}
+
 /// Parses a color string.
 /// 
 /// Useful for interpreting user input.
@@ -406,6 +848,12 @@ pub fn color_to_string_4(c: (u8, u8, u8), prepend: &str) -> String
 /// 
 /// "red", "0,0,0", "0, 0, 0",
 /// 
+/// "#1e90ff", "#abc", "0x1e90ff", "1e90ff",
+/// 
+/// "rgb(30 144 255)", "hsl(210, 100%, 56%)", "hwb(210 12% 0% / 50%)",
+/// 
+/// "hsv(210, 88%, 100%)", "color-mix(in oklch, red 40%, blue)",
+/// 
 /// "darker", "darker2", "darker3",
 /// 
 /// "darker4", "darker5",
@@ -414,19 +862,47 @@ pub fn color_to_string_4(c: (u8, u8, u8), prepend: &str) -> String
 /// 
 /// "lighter4", "lighter5",
 /// 
+/// "saturate", "saturate2" ... "saturate5",
+/// 
+/// "desaturate", "desaturate2" ... "desaturate5",
+/// 
 /// or "random".
 /// 
-/// The input is lowercased and the whitespaces are removed.
+/// Colors can be followed by modifiers like
+/// "steelblue darker2 rotate(30)" or
+/// "#336699 | lighter 15% | alpha 0.5",
+/// check the try_parse_color definition for the grammar.
+/// 
+/// The input is lowercased and the whitespaces inside a color are removed.
 /// 
 /// darker3 turns it 3 times darker than darker.
 /// 
-/// Percentages for darker and lighter are hardcoded:
+/// Each step of darker, lighter, saturate
+/// and desaturate is 10%, up to 5 steps.
+/// 
+/// Any other amount can be used with
+/// "darker:37", "darker 12.5%" or "lighter(20)",
+/// every amount is a percentage.
 /// 
-/// PERCENT_1: f64 = 10.0;
+/// Hex values can have 3, 4, 6 or 8 digits.
 /// 
-/// PERCENT_2: f64 = 20.0;
+/// The alpha digits of the 4 and 8 digit forms are ignored,
+/// use parse_rgba to get them.
 /// 
-/// And so on... up to PERCENT_5.
+/// Color names are checked before bare hex values,
+/// so "tan" is a name but "bee" is a hex value.
+/// 
+/// Functional notations follow the CSS Color Level 4 syntax,
+/// check the try_parse_color definition for details.
+/// 
+/// Invalid RGB components are read as 0
+/// and anything else that can't be parsed
+/// returns the reference.
+/// 
+/// Use try_parse_color to get an error instead.
+/// 
+/// Use parse_color_with to make darker and lighter
+/// work in OKLCh instead of HSL, or to change the steps.
 /// 
 /// # Examples
 /// 
@@ -434,43 +910,91 @@ pub fn color_to_string_4(c: (u8, u8, u8), prepend: &str) -> String
 /// use colorskill::parse_color;
 /// let c = parse_color("blue", (0, 0, 0));
 /// let c = parse_color("34,65,39", (0, 0, 0));
+/// let c = parse_color("#1e90ff", (0, 0, 0));
+/// let c = parse_color("hsl(120deg 100% 25%)", (0, 0, 0));
 /// let c = parse_color("darker", (10, 34, 50));
 /// let c = parse_color("lighter3", (210, 87, 130));
+/// let c = parse_color("darker:37", (210, 87, 130));
+/// let c = parse_color("desaturate2", (210, 87, 130));
+/// let c = parse_color("steelblue darker2 rotate(30)", (0, 0, 0));
 /// let c = parse_color("random", (0, 0, 0));
 /// ```
 pub fn parse_color(s: &str, reference: (u8, u8, u8)) -> (u8, u8, u8)
 {
-    let cs = clean_string(s);
+    parse_color_with(s, reference, &ParseOptions::default())
+}
 
-    match &cs[..]
+/// Parses a color string with options.
+/// 
+/// Works like parse_color, but the options
+/// can change how keywords like "darker" work.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::{parse_color_with, ParseOptions, LightnessModel};
+/// let options = ParseOptions {lightness: LightnessModel::OKLCh, ..Default::default()};
+/// let c = parse_color_with("darker2", (10, 34, 50), &options);
+/// ```
+pub fn parse_color_with(s: &str, reference: (u8, u8, u8), options: &ParseOptions) -> (u8, u8, u8)
+{
+    match try_parse_color_with(s, reference, options)
     {
-        // Check if color should be darker or lighter
-        "darker" | "darker1" => make_color_darker(reference, PERCENT_1),
-        "darker2" => make_color_darker(reference, PERCENT_2),
-        "darker3" => make_color_darker(reference, PERCENT_3),
-        "darker4" => make_color_darker(reference, PERCENT_4),
-        "darker5" => make_color_darker(reference, PERCENT_5),
-        "lighter" | "lighter1" => make_color_lighter(reference, PERCENT_1),
-        "lighter2" => make_color_lighter(reference, PERCENT_2),
-        "lighter3" => make_color_lighter(reference, PERCENT_3),
-        "lighter4" => make_color_lighter(reference, PERCENT_4),
-        "lighter5" => make_color_lighter(reference, PERCENT_5),
-        "random" => random_color(),
-        _ => 
+        Ok(c) => c,
+        Err(_) =>
         {
-            // If not then check if it's an RGB value
-            if cs.contains(',')
-            {
-                let v: Vec<u8> = cs.split(',')
-                    .map(|n| n.parse::<u8>().unwrap_or(0)).collect();
+            parse_lenient_triple(&clean_string(s)).unwrap_or(reference)
+        }
+    }
+}
 
-                if v.len() != 3 {return reference} (v[0], v[1], v[2])
-            }
+/// Parses a color string with alpha.
+/// 
+/// Works like parse_color but returns an
+/// RGBA tuple and reads the alpha of values
+/// like "#1e90ff80" and "rgba(30, 144, 255, 0.5)".
+/// 
+/// Colors without alpha are opaque,
+/// keywords like darker and saturate keep the reference alpha,
+/// and anything that can't be parsed
+/// returns the reference.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use colorskill::parse_rgba;
+/// let c = parse_rgba("#1e90ff80", (0, 0, 0, 255));
+/// let c = parse_rgba("hsl(120deg 100% 25% / 20%)", (0, 0, 0, 255));
+/// let c = parse_rgba("darker", (10, 34, 50, 128));
+/// ```
+pub fn parse_rgba(s: &str, reference: (u8, u8, u8, u8)) -> (u8, u8, u8, u8)
+{
+    parse_rgba_with(s, reference, &ParseOptions::default())
+}
 
-            else
+/// Parses a color string with alpha and options.
+/// 
+/// Works like parse_rgba, but the options
+/// can change how keywords like "darker" work.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::{parse_rgba_with, ParseOptions, LightnessModel};
+/// let options = ParseOptions {lightness: LightnessModel::OKLCh, ..Default::default()};
+/// let c = parse_rgba_with("darker2", (10, 34, 50, 128), &options);
+/// ```
+pub fn parse_rgba_with(s: &str, reference: (u8, u8, u8, u8), options: &ParseOptions) -> (u8, u8, u8, u8)
+{
+    match try_parse_rgba_with(s, reference, options)
+    {
+        Ok(c) => c,
+        Err(_) =>
+        {
+            match parse_lenient_triple(&clean_string(s))
             {
-                // If not then check if it's a color name
-                color_name_to_rgb(&cs, reference)
+                Some(c) => (c.0, c.1, c.2, 255),
+                None => reference
             }
         }
     }
@@ -496,6 +1020,18 @@ mod tests
     }
 
     #[test]
+    fn lookup_test()
+    {
+        assert_eq!(lookup_color_name("Rebecca Purple"), Some((102, 51, 153)));
+        assert_eq!(lookup_color_name("slategrey"), Some((112, 128, 144)));
+        assert_eq!(lookup_color_name("transparent"), Some((0, 0, 0)));
+        assert_eq!(lookup_color_name("InvalidColor"), None);
+        assert_eq!(color_names().count(), 149);
+        assert!(color_names().all(|(n, rgb)| lookup_color_name(n) == Some(rgb)));
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn name_exists_test()
     {
         assert_eq!(check_color_name("red"), true);
@@ -504,6 +1040,30 @@ mod tests
         assert_eq!(check_color_name("InvalidColor"), false);
     }
 
+    #[test]
+    fn suggest_test()
+    {
+        assert_eq!(suggest_color_names("lightgoldenrod", 3), vec!["lightgoldenrodyellow", "goldenrod"]);
+        assert_eq!(suggest_color_names("Corn Flower", 3), vec!["cornflowerblue"]);
+        assert_eq!(suggest_color_names("bleu", 3), vec!["blue"]);
+        assert_eq!(suggest_color_names("darkbluey", 1), vec!["darkblue"]);
+        assert_eq!(suggest_color_names("lightgr", 3), vec!["lightgray", "lightgrey", "lightgreen"]);
+        assert!(suggest_color_names("qqqqqqqq", 3).is_empty());
+        assert!(suggest_color_names("", 3).is_empty());
+    }
+
+    #[test]
+    fn reverse_name_test()
+    {
+        assert_eq!(nearest_color_name((255, 99, 71)), ("tomato", 0.0));
+        assert_eq!(nearest_color_name((250, 5, 5)).0, "red");
+        assert_eq!(nearest_color_name((0, 0, 0)), ("black", 0.0));
+        assert_eq!(nearest_color_name((30, 144, 250)).0, "dodgerblue");
+        assert_eq!(exact_color_name((0, 255, 255)), Some("aqua"));
+        assert_eq!(exact_color_name((0, 0, 0)), Some("black"));
+        assert_eq!(exact_color_name((1, 2, 3)), None);
+    }
+
     #[test]
     fn string_test()
     {
@@ -515,6 +1075,22 @@ mod tests
         assert_eq!(color_to_string_4((255, 99, 71), "RGB"), "RGB(255, 99, 71)".to_string());
     }
 
+    #[test]
+    fn hsv_test()
+    {
+        let hsv = rgb_to_hsv((51, 77, 102));
+        assert_eq!((round_float(hsv.0), round_float(hsv.1), round_float(hsv.2)), (209.41, 50.0, 40.0));
+        assert_eq!(rgb_to_hsv((0, 0, 0)), (0.0, 0.0, 0.0));
+        assert_eq!(rgb_to_hsv((255, 0, 255)), (300.0, 100.0, 100.0));
+        assert_eq!(hsv_to_rgb((209.41, 50.0, 40.0)), (51, 77, 102));
+        assert_eq!(hsv_to_rgb((0.0, 100.0, 100.0)), (255, 0, 0));
+        assert_eq!(get_color_hsv_saturation((120, 239, 64)), 73.22);
+        assert_eq!(get_color_value((120, 239, 64)), 93.73);
+        assert_eq!(color_to_hsv_string((51, 77, 102)), "hsv(209.41, 50%, 40%)");
+        assert_eq!(parse_color("hsv(209.41, 50%, 40%)", (0, 0, 0)), (51, 77, 102));
+        assert_eq!(parse_color("hsb(0 100% 100%)", (0, 0, 0)), (255, 0, 0));
+    }
+
     #[test]
     fn random_test()
     {
@@ -539,6 +1115,55 @@ mod tests
         assert_eq!(parse_color("lighter3", (95, 158, 160)), (191, 216, 217));
         assert_eq!(parse_color("lighter4", (95, 158, 160)), (223, 236, 236));
         assert_eq!(parse_color("lighter5", (95, 158, 160)), (255, 255, 255));
+
+        assert_eq!(parse_color("300,12,x", (1, 2, 3)), (0, 12, 0));
+        assert_eq!(parse_color("1,2", (1, 2, 3)), (1, 2, 3));
+        assert_eq!(parse_color("InvalidColor", (1, 2, 3)), (1, 2, 3));
+
+        let cs = color_to_string_4((255, 99, 71), "rgb");
+        assert_eq!(parse_color(&cs, (0, 0, 0)), (255, 99, 71));
+        assert_eq!(parse_color("hsl(9, 100%, 64%)", (0, 0, 0)), (255, 99, 71));
+        assert_eq!(parse_color("color-mix(in srgb, red, blue)", (0, 0, 0)), (128, 0, 128));
+        assert_eq!(parse_color("color-mix(in srgb, red)", (1, 2, 3)), (1, 2, 3));
+    }
+
+    #[test]
+    fn hex_test()
+    {
+        assert_eq!(parse_color("#1e90ff", (0, 0, 0)), (30, 144, 255));
+        assert_eq!(parse_color("#1E90FF", (0, 0, 0)), (30, 144, 255));
+        assert_eq!(parse_color("0x1e90ff", (0, 0, 0)), (30, 144, 255));
+        assert_eq!(parse_color("1e90ff", (0, 0, 0)), (30, 144, 255));
+        assert_eq!(parse_color("#abc", (0, 0, 0)), (170, 187, 204));
+        assert_eq!(parse_color("#abcd", (0, 0, 0)), (170, 187, 204));
+        assert_eq!(parse_color("#1e90ff80", (0, 0, 0)), (30, 144, 255));
+        assert_eq!(parse_color("tan", (0, 0, 0)), (210, 180, 140));
+        assert_eq!(parse_color("#12345", (1, 2, 3)), (1, 2, 3));
+
+        assert_eq!(color_to_hex((30, 144, 255), false, false), "#1e90ff");
+        assert_eq!(color_to_hex((30, 144, 255), true, false), "#1E90FF");
+        assert_eq!(color_to_hex((30, 144, 255), false, true), "#1e90ff");
+        assert_eq!(color_to_hex((170, 187, 204), false, true), "#abc");
+        assert_eq!(color_to_hex((255, 0, 0), true, true), "#F00");
+        assert_eq!(color_to_hex((255, 0, 0), true, false), "#FF0000");
+
+        assert_eq!(color_to_hex_alpha((30, 144, 255, 128), false, false), "#1e90ff80");
+        assert_eq!(color_to_hex_alpha((255, 0, 0, 136), true, true), "#F008");
+        assert_eq!(color_to_hex_alpha((255, 0, 0, 128), false, true), "#ff000080");
+    }
+
+    #[test]
+    fn rgba_test()
+    {
+        assert_eq!(parse_rgba("#1e90ff80", (0, 0, 0, 255)), (30, 144, 255, 128));
+        assert_eq!(parse_rgba("rgba(30, 144, 255, 50%)", (0, 0, 0, 255)), (30, 144, 255, 128));
+        assert_eq!(parse_rgba("1,2,x", (0, 0, 0, 10)), (1, 2, 0, 255));
+        assert_eq!(parse_rgba("invalid", (1, 2, 3, 4)), (1, 2, 3, 4));
+        assert_eq!(parse_rgba("lighter", (0, 0, 0, 4)), (26, 26, 26, 4));
+
+        assert_eq!(composite_over((255, 0, 0, 128), (255, 255, 255)), (255, 127, 127));
+        assert_eq!(composite_over((255, 0, 0, 0), (0, 0, 255)), (0, 0, 255));
+        assert_eq!(composite_over((12, 34, 56, 255), (0, 0, 255)), (12, 34, 56));
     }
 
     #[test]
@@ -550,6 +1175,54 @@ mod tests
         assert_eq!(change_color_lightness((184,134,11), false, 15.0), (242, 180, 30));
     }
 
+    #[test]
+    fn hsl_adjust_test()
+    {
+        let c = (70, 130, 180);
+        assert_eq!(saturate_color(c, 20.0), (45, 132, 205));
+        assert_eq!(desaturate_color(c, 20.0), (95, 128, 155));
+        assert_eq!(change_color_saturation(c, true, 100.0), grayscale_color(c));
+        assert_eq!(set_color_saturation(c, 150.0), set_color_saturation(c, 100.0));
+        assert_eq!(rotate_color_hue(c, 90.0), (175, 70, 180));
+        assert_eq!(rotate_color_hue(c, -450.0), (75, 180, 70));
+        assert_eq!(set_color_hue(c, 360.0), (180, 70, 70));
+        assert_eq!(set_color_lightness(c, 80.0), (182, 206, 226));
+        assert_eq!(grayscale_color(c), (125, 125, 125));
+        assert_eq!(complement_color(c), (180, 120, 70));
+        assert_eq!(invert_color(c), (185, 125, 75));
+        assert_eq!(invert_color(invert_color(c)), c);
+    }
+
+    #[test]
+    fn lightness_model_test()
+    {
+        let c = (95, 158, 160);
+        assert_eq!(change_color_lightness_with(c, true, 20.0, LightnessModel::HSL), make_color_darker(c, 20.0));
+        assert_eq!(make_color_darker_with(c, 20.0, LightnessModel::OKLCh), (33, 98, 101));
+        assert_eq!(make_color_lighter_with(c, 20.0, LightnessModel::OKLCh), (157, 222, 224));
+        assert_eq!(make_color_lighter_with(c, 100.0, LightnessModel::HSL), (255, 255, 255));
+        assert_eq!(make_color_darker_with(c, 100.0, LightnessModel::OKLCh), (0, 0, 0));
+
+        // The OKLCh hue stays close to the original
+        let blue = (30, 80, 200);
+        let hue = OKLCh::from(RGB::from_tuple(blue)).get_h();
+        let lighter = make_color_lighter_with(blue, 15.0, LightnessModel::OKLCh);
+        assert!((OKLCh::from(RGB::from_tuple(lighter)).get_h() - hue).abs() < 0.1);
+
+        // Out of gamut results shift less than when clipping
+        let lighter = make_color_lighter_with(blue, 30.0, LightnessModel::OKLCh);
+        let lch = OKLCh::from(RGB::from_tuple(blue));
+        let clipped = RGB::from(OKLCh::new(lch.get_l() + 0.3, lch.get_c(), hue));
+        let shift = (OKLCh::from(RGB::from_tuple(lighter)).get_h() - hue).abs();
+        assert!(shift < 3.0);
+        assert!(shift < (OKLCh::from(clipped).get_h() - hue).abs());
+
+        let options = ParseOptions {lightness: LightnessModel::OKLCh, ..Default::default()};
+        assert_eq!(parse_color_with("darker2", c, &options), (33, 98, 101));
+        assert_eq!(parse_color_with("red", c, &options), (255, 0, 0));
+        assert_eq!(parse_color_with("darker2", c, &ParseOptions::default()), parse_color("darker2", c));
+    }
+
     #[test]
     fn hsl_test()
     {
@@ -0,0 +1,294 @@
+use crate::*;
+
+// Chroma and saturation below these values
+// make the hue powerless (missing) when mixing
+const HSL_EPSILON: f64 = 0.0001;
+const LCH_EPSILON: f64 = 0.01;
+const OKLCH_EPSILON: f64 = 0.0001;
+
+/// A color space used to mix colors.
+///
+/// Lab and LCh use the D50 white
+/// like the CSS color-mix() function.
+///
+/// The default is OKLab, which gives
+/// smooth mixes without hue shifts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ColorSpace
+{
+    SRGB,
+    LinearSRGB,
+    HSL,
+    Lab,
+    LCh,
+    #[default]
+    OKLab,
+    OKLCh
+}
+
+/// The way hues are interpolated in the
+/// polar spaces (HSL, LCh and OKLCh).
+///
+/// These follow the CSS hue interpolation methods:
+///
+/// Shorter takes the short way around the circle,
+/// Longer takes the long way, and Increasing and
+/// Decreasing always go in one direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum HueInterpolation
+{
+    #[default]
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing
+}
+
+/// Mixes two colors in a color space.
+///
+/// t is the amount of the second color
+/// from 0 to 1, so 0 returns the first color,
+/// 1 returns the second and 0.5 is halfway.
+///
+/// Hues take the shorter way around,
+/// use mix_with to change it.
+///
+/// Mixes outside of the sRGB gamut are
+/// mapped back with chroma reduction.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{mix, ColorSpace};
+/// let c = mix((255, 0, 0), (0, 0, 255), 0.5, ColorSpace::SRGB);
+/// assert_eq!(c, (128, 0, 128));
+/// ```
+pub fn mix(a: (u8, u8, u8), b: (u8, u8, u8), t: f64, space: ColorSpace) -> (u8, u8, u8)
+{
+    mix_with(a, b, t, space, HueInterpolation::Shorter)
+}
+
+/// Mixes two colors in a color space
+/// with a hue interpolation method.
+///
+/// Works like mix, the method is only
+/// used by the HSL, LCh and OKLCh spaces.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{mix_with, ColorSpace, HueInterpolation};
+/// let c = mix_with((255, 0, 0), (0, 0, 255), 0.5, ColorSpace::HSL, HueInterpolation::Longer);
+/// assert_eq!(c, (0, 255, 0));
+/// ```
+pub fn mix_with(a: (u8, u8, u8), b: (u8, u8, u8), t: f64, space: ColorSpace, hue: HueInterpolation) -> (u8, u8, u8)
+{
+    let c = mix_rgba((a.0, a.1, a.2, 255), (b.0, b.1, b.2, 255), t, space, hue);
+    (c.0, c.1, c.2)
+}
+
+// Mixes two RGBA colors with premultiplied alpha
+// like the CSS color-mix() function
+#[allow(clippy::suspicious_else_formatting)]
+pub(crate) fn mix_rgba(a: (u8, u8, u8, u8), b: (u8, u8, u8, u8), t: f64, space: ColorSpace, hue: HueInterpolation)
+    -> (u8, u8, u8, u8)
+{
+    let t = t.clamp(0.0, 1.0);
+    let (aa, ab) = (f64::from(a.3) / 255.0, f64::from(b.3) / 255.0);
+    let alpha = aa + (ab - aa) * t;
+    if alpha <= 0.0 {return (0, 0, 0, 0)}
+
+    let mut ca = to_space((a.0, a.1, a.2), space);
+    let mut cb = to_space((b.0, b.1, b.2), space);
+    let hue_index = hue_index(space);
+
+    if let Some(i) = hue_index
+    {
+        // A powerless hue takes the hue of the other color
+        match (ca[i].is_nan(), cb[i].is_nan())
+        {
+            (true, true) => {ca[i] = 0.0; cb[i] = 0.0},
+            (true, false) => ca[i] = cb[i],
+            (false, true) => cb[i] = ca[i],
+            _ => {}
+        }
+
+        fix_hues(&mut ca[i], &mut cb[i], hue);
+    }
+
+    let mut v = [0.0; 3];
+
+    for i in 0..3
+    {
+        v[i] = if Some(i) == hue_index
+        {
+            (ca[i] + (cb[i] - ca[i]) * t).rem_euclid(360.0)
+        }
+
+        else
+        {
+            (ca[i] * aa + (cb[i] * ab - ca[i] * aa) * t) / alpha
+        };
+    }
+
+    let c = from_space(v, space);
+    (c.0, c.1, c.2, alpha_to_u8(alpha))
+}
+
+// Gets the position of the hue in a color space
+fn hue_index(space: ColorSpace) -> Option<usize>
+{
+    match space
+    {
+        ColorSpace::HSL => Some(0),
+        ColorSpace::LCh | ColorSpace::OKLCh => Some(2),
+        _ => None
+    }
+}
+
+// Moves the hues so a plain interpolation
+// between them follows the method
+fn fix_hues(h1: &mut f64, h2: &mut f64, method: HueInterpolation)
+{
+    let d = *h2 - *h1;
+
+    match method
+    {
+        HueInterpolation::Shorter =>
+        {
+            if d > 180.0 {*h1 += 360.0}
+            else if d < -180.0 {*h2 += 360.0}
+        },
+        HueInterpolation::Longer =>
+        {
+            if d > 0.0 && d < 180.0 {*h1 += 360.0}
+            else if d > -180.0 && d <= 0.0 {*h2 += 360.0}
+        },
+        HueInterpolation::Increasing => if d < 0.0 {*h2 += 360.0},
+        HueInterpolation::Decreasing => if d > 0.0 {*h1 += 360.0}
+    }
+}
+
+// Converts a color into the components of a space,
+// powerless hues are NaN
+fn to_space(t: (u8, u8, u8), space: ColorSpace) -> [f64; 3]
+{
+    let c = RGB::from_tuple(t);
+    let powerless = |h: f64, chroma: f64, epsilon: f64| if chroma < epsilon {f64::NAN} else {h};
+
+    match space
+    {
+        ColorSpace::SRGB =>
+        {
+            [f64::from(t.0) / 255.0, f64::from(t.1) / 255.0, f64::from(t.2) / 255.0]
+        },
+        ColorSpace::LinearSRGB =>
+        {
+            let (r, g, b) = rgb_to_linear(t);
+            [r, g, b]
+        },
+        ColorSpace::HSL =>
+        {
+            let hsl = get_hsl(t);
+            let (s, l) = (hsl.get_saturation() / 100.0, hsl.get_lightness() / 100.0);
+            [powerless(hsl.get_hue(), s, HSL_EPSILON), s, l]
+        },
+        ColorSpace::Lab =>
+        {
+            let (l, a, b) = Lab::from(c).to_white(WhitePoint::D50).get_tuple();
+            [l, a, b]
+        },
+        ColorSpace::LCh =>
+        {
+            let (l, ch, h) = LCh::from(c).to_white(WhitePoint::D50).get_tuple();
+            [l, ch, powerless(h, ch, LCH_EPSILON)]
+        },
+        ColorSpace::OKLab =>
+        {
+            let (l, a, b) = OKLab::from(c).get_tuple();
+            [l, a, b]
+        },
+        ColorSpace::OKLCh =>
+        {
+            let (l, ch, h) = OKLCh::from(c).get_tuple();
+            [l, ch, powerless(h, ch, OKLCH_EPSILON)]
+        }
+    }
+}
+
+// Converts the components of a space into a color
+// mapping it into the sRGB gamut if needed
+fn from_space(v: [f64; 3], space: ColorSpace) -> (u8, u8, u8)
+{
+    let map = |c: OKLCh| map_to_gamut(c, GamutMapping::Chroma).get_tuple();
+    let from_xyz = |c: XYZ| map(OKLCh::from(OKLab::from(c)));
+
+    match space
+    {
+        ColorSpace::SRGB => unit_rgb_to_tuple((v[0], v[1], v[2])),
+        ColorSpace::LinearSRGB => linear_to_rgb((v[0], v[1], v[2])),
+        ColorSpace::HSL => unit_rgb_to_tuple(hsl_to_unit_rgb(v[0], v[1], v[2])),
+        ColorSpace::Lab => from_xyz(XYZ::from(Lab::with_white(v[0], v[1], v[2], WhitePoint::D50))),
+        ColorSpace::LCh => from_xyz(XYZ::from(Lab::from(LCh::with_white(v[0], v[1], v[2], WhitePoint::D50)))),
+        ColorSpace::OKLab => map(OKLCh::from(OKLab::new(v[0], v[1], v[2]))),
+        ColorSpace::OKLCh => map(OKLCh::new(v[0], v[1], v[2]))
+    }
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn mix_test()
+    {
+        let (red, blue) = ((255, 0, 0), (0, 0, 255));
+        assert_eq!(mix(red, blue, 0.0, ColorSpace::OKLCh), red);
+        assert_eq!(mix(red, blue, 1.0, ColorSpace::OKLCh), blue);
+        assert_eq!(mix(red, blue, 0.5, ColorSpace::SRGB), (128, 0, 128));
+        assert_eq!(mix(red, blue, 0.5, ColorSpace::LinearSRGB), (188, 0, 188));
+        assert_eq!(mix(red, blue, 0.5, ColorSpace::HSL), (255, 0, 255));
+        assert_eq!(mix(red, blue, 0.5, ColorSpace::Lab), (193, 0, 136));
+        assert_eq!(mix(red, blue, 0.5, ColorSpace::OKLab), (140, 83, 162));
+        assert_eq!(mix(red, blue, 0.5, ColorSpace::OKLCh), (183, 0, 190));
+
+        // Mixing with white or gray keeps the hue
+        let c = mix((70, 130, 180), (255, 255, 255), 0.5, ColorSpace::OKLCh);
+        assert!((OKLCh::from(RGB::from_tuple(c)).get_h() - OKLCh::from(RGB::new(70, 130, 180)).get_h()).abs() < 2.0);
+        assert_eq!(mix((0, 0, 0), (255, 255, 255), 0.5, ColorSpace::HSL), (128, 128, 128));
+    }
+
+    #[test]
+    fn hue_test()
+    {
+        let (red, blue) = ((255, 0, 0), (0, 0, 255));
+        assert_eq!(mix_with(red, blue, 0.5, ColorSpace::HSL, HueInterpolation::Shorter), (255, 0, 255));
+        assert_eq!(mix_with(red, blue, 0.5, ColorSpace::HSL, HueInterpolation::Longer), (0, 255, 0));
+        assert_eq!(mix_with(red, blue, 0.5, ColorSpace::HSL, HueInterpolation::Increasing), (0, 255, 0));
+        assert_eq!(mix_with(red, blue, 0.5, ColorSpace::HSL, HueInterpolation::Decreasing), (255, 0, 255));
+
+        let (mut h1, mut h2) = (350.0, 10.0);
+        fix_hues(&mut h1, &mut h2, HueInterpolation::Shorter);
+        assert_eq!((h1, h2), (350.0, 370.0));
+        let (mut h1, mut h2) = (10.0, 20.0);
+        fix_hues(&mut h1, &mut h2, HueInterpolation::Longer);
+        assert_eq!((h1, h2), (370.0, 20.0));
+        let (mut h1, mut h2) = (20.0, 10.0);
+        fix_hues(&mut h1, &mut h2, HueInterpolation::Increasing);
+        assert_eq!((h1, h2), (20.0, 370.0));
+    }
+
+    #[test]
+    fn alpha_test()
+    {
+        // Premultiplied, so a transparent color
+        // does not pull the mix towards black
+        let c = mix_rgba((255, 0, 0, 255), (0, 0, 0, 0), 0.5, ColorSpace::SRGB, HueInterpolation::Shorter);
+        assert_eq!(c, (255, 0, 0, 128));
+        let c = mix_rgba((0, 0, 0, 0), (0, 0, 0, 0), 0.5, ColorSpace::OKLab, HueInterpolation::Shorter);
+        assert_eq!(c, (0, 0, 0, 0));
+    }
+}
@@ -0,0 +1,349 @@
+use crate::*;
+
+// Linear sRGB to LMS matrix
+const RGB_TO_LMS: [[f64; 3]; 3] =
+[
+    [0.4122214708, 0.5363325363, 0.0514459929],
+    [0.2119034982, 0.6806995451, 0.1073969566],
+    [0.0883024619, 0.2817188376, 0.6299787005]
+];
+
+// Non linear LMS to OKLab matrix
+const LMS_TO_OKLAB: [[f64; 3]; 3] =
+[
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660]
+];
+
+// OKLab to non linear LMS matrix
+const OKLAB_TO_LMS: [[f64; 3]; 3] =
+[
+    [1.0, 0.3963377774, 0.2158037573],
+    [1.0, -0.1055613458, -0.0638541728],
+    [1.0, -0.0894841775, -1.2914855480]
+];
+
+// LMS to linear sRGB matrix
+const LMS_TO_RGB: [[f64; 3]; 3] =
+[
+    [4.0767416621, -3.3077115913, 0.2309699292],
+    [-1.2684380046, 2.6097574011, -0.3413193965],
+    [-0.0041960863, -0.7034186147, 1.7076147010]
+];
+
+/// A color in the OKLab color space.
+///
+/// L goes from 0 to 1 and a and b
+/// are usually between -0.4 and 0.4.
+///
+/// OKLab is perceptually uniform and keeps
+/// hues stable when changing lightness.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OKLab
+{
+    l: f64,
+    a: f64,
+    b: f64
+}
+
+impl OKLab
+{
+    /// Makes a new OKLab from three values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLab;
+    /// let c = OKLab::new(0.628, 0.2249, 0.1258);
+    /// ```
+    pub fn new(l: f64, a: f64, b: f64) -> OKLab
+    {
+        OKLab
+        {
+            l, a, b
+        }
+    }
+
+    /// Gets the L value (lightness).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLab;
+    /// let l = OKLab::new(0.628, 0.2249, 0.1258).get_l();
+    /// ```
+    pub fn get_l(&self) -> f64
+    {
+        self.l
+    }
+
+    /// Gets the a value (green to red).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLab;
+    /// let a = OKLab::new(0.628, 0.2249, 0.1258).get_a();
+    /// ```
+    pub fn get_a(&self) -> f64
+    {
+        self.a
+    }
+
+    /// Gets the b value (blue to yellow).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLab;
+    /// let b = OKLab::new(0.628, 0.2249, 0.1258).get_b();
+    /// ```
+    pub fn get_b(&self) -> f64
+    {
+        self.b
+    }
+
+    /// Gets the OKLab values in a tuple.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLab;
+    /// let t = OKLab::new(0.628, 0.2249, 0.1258).get_tuple();
+    /// ```
+    pub fn get_tuple(&self) -> (f64, f64, f64)
+    {
+        (self.l, self.a, self.b)
+    }
+
+    /// Makes an OKLab from linear sRGB values.
+    ///
+    /// See the rgb_to_linear definition.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLab;
+    /// let c = OKLab::from_linear_rgb((1.0, 0.0, 0.0));
+    /// ```
+    pub fn from_linear_rgb(t: (f64, f64, f64)) -> OKLab
+    {
+        let lms = multiply(&RGB_TO_LMS, t);
+        let (l, a, b) = multiply(&LMS_TO_OKLAB, (lms.0.cbrt(), lms.1.cbrt(), lms.2.cbrt()));
+        OKLab::new(l, a, b)
+    }
+
+    /// Returns the linear sRGB values.
+    ///
+    /// The values are not clamped so
+    /// colors outside of the sRGB gamut
+    /// go below 0 or above 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLab;
+    /// let t = OKLab::new(0.628, 0.2249, 0.1258).to_linear_rgb();
+    /// ```
+    pub fn to_linear_rgb(&self) -> (f64, f64, f64)
+    {
+        let lms = multiply(&OKLAB_TO_LMS, self.get_tuple());
+        multiply(&LMS_TO_RGB, (lms.0.powi(3), lms.1.powi(3), lms.2.powi(3)))
+    }
+}
+
+impl From<RGB> for OKLab
+{
+    fn from(c: RGB) -> OKLab
+    {
+        OKLab::from_linear_rgb(rgb_to_linear(c.get_tuple()))
+    }
+}
+
+impl From<OKLab> for RGB
+{
+    fn from(c: OKLab) -> RGB
+    {
+        RGB::from_tuple(linear_to_rgb(c.to_linear_rgb()))
+    }
+}
+
+impl From<XYZ> for OKLab
+{
+    fn from(c: XYZ) -> OKLab
+    {
+        OKLab::from_linear_rgb(c.to_linear_rgb())
+    }
+}
+
+impl From<OKLab> for XYZ
+{
+    fn from(c: OKLab) -> XYZ
+    {
+        XYZ::from_linear_rgb(c.to_linear_rgb())
+    }
+}
+
+/// A color in the cylindrical OKLCh form of OKLab.
+///
+/// L goes from 0 to 1, C is the chroma
+/// (usually below 0.4) and h is the hue in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OKLCh
+{
+    l: f64,
+    c: f64,
+    h: f64
+}
+
+impl OKLCh
+{
+    /// Makes a new OKLCh from three values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLCh;
+    /// let c = OKLCh::new(0.628, 0.2577, 29.23);
+    /// ```
+    pub fn new(l: f64, c: f64, h: f64) -> OKLCh
+    {
+        OKLCh
+        {
+            l, c, h: h.rem_euclid(360.0)
+        }
+    }
+
+    /// Gets the L value (lightness).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLCh;
+    /// let l = OKLCh::new(0.628, 0.2577, 29.23).get_l();
+    /// ```
+    pub fn get_l(&self) -> f64
+    {
+        self.l
+    }
+
+    /// Gets the C value (chroma).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLCh;
+    /// let c = OKLCh::new(0.628, 0.2577, 29.23).get_c();
+    /// ```
+    pub fn get_c(&self) -> f64
+    {
+        self.c
+    }
+
+    /// Gets the h value (hue degrees).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLCh;
+    /// let h = OKLCh::new(0.628, 0.2577, 29.23).get_h();
+    /// ```
+    pub fn get_h(&self) -> f64
+    {
+        self.h
+    }
+
+    /// Gets the OKLCh values in a tuple.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::OKLCh;
+    /// let t = OKLCh::new(0.628, 0.2577, 29.23).get_tuple();
+    /// ```
+    pub fn get_tuple(&self) -> (f64, f64, f64)
+    {
+        (self.l, self.c, self.h)
+    }
+}
+
+impl From<OKLab> for OKLCh
+{
+    fn from(c: OKLab) -> OKLCh
+    {
+        let (l, a, b) = c.get_tuple();
+        OKLCh::new(l, a.hypot(b), b.atan2(a).to_degrees())
+    }
+}
+
+impl From<OKLCh> for OKLab
+{
+    fn from(c: OKLCh) -> OKLab
+    {
+        let h = c.h.to_radians();
+        OKLab::new(c.l, c.c * h.cos(), c.c * h.sin())
+    }
+}
+
+impl From<RGB> for OKLCh
+{
+    fn from(c: RGB) -> OKLCh
+    {
+        OKLCh::from(OKLab::from(c))
+    }
+}
+
+impl From<OKLCh> for RGB
+{
+    fn from(c: OKLCh) -> RGB
+    {
+        RGB::from(OKLab::from(c))
+    }
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn round4(t: (f64, f64, f64)) -> (f64, f64, f64)
+    {
+        let f = |n: f64| (n * 10000.0).round() / 10000.0;
+        (f(t.0), f(t.1), f(t.2))
+    }
+
+    #[test]
+    fn oklab_test()
+    {
+        // Published values for sRGB red and white
+        assert_eq!(round4(OKLab::from(RGB::new(255, 0, 0)).get_tuple()), (0.628, 0.2249, 0.1258));
+        assert_eq!(round4(OKLab::from(RGB::new(255, 255, 255)).get_tuple()), (1.0, 0.0, 0.0));
+        assert_eq!(round4(OKLab::from(RGB::new(0, 0, 255)).get_tuple()), (0.452, -0.0325, -0.3115));
+
+        for t in [(255, 0, 0), (30, 144, 255), (12, 200, 99), (0, 0, 0), (1, 1, 1)].iter()
+        {
+            let c = RGB::from_tuple(*t);
+            assert_eq!(RGB::from(OKLab::from(c)), c);
+        }
+    }
+
+    #[test]
+    fn oklch_test()
+    {
+        assert_eq!(round4(OKLCh::from(RGB::new(255, 0, 0)).get_tuple()), (0.628, 0.2577, 29.2339));
+        assert_eq!(OKLCh::new(0.5, 0.1, 370.0).get_h(), 10.0);
+
+        // Colors outside of sRGB survive the trip through XYZ
+        let wide = OKLab::new(0.7, -0.3, 0.1);
+        assert_eq!(round4(OKLab::from(XYZ::from(wide)).get_tuple()), (0.7, -0.3, 0.1));
+
+        for t in [(255, 0, 0), (30, 144, 255), (12, 200, 99), (128, 128, 128)].iter()
+        {
+            let c = RGB::from_tuple(*t);
+            assert_eq!(RGB::from(OKLCh::from(c)), c);
+        }
+    }
+}
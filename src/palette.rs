@@ -0,0 +1,447 @@
+use crate::*;
+use crate::delta_e::cie76_factors;
+use std::sync::OnceLock;
+
+/// An ordered list of colors, each one
+/// with an optional name, like design tokens.
+///
+/// Nearest color queries use a k-d tree of the
+/// D65 Lab values, built on the first query
+/// after a change, so they stay fast
+/// with thousands of colors.
+///
+/// The results are the same as comparing every
+/// color with the metric, ties go to the
+/// color that was added first.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{Palette, Metric, RGB};
+/// let mut p = Palette::new();
+/// p.add_named("primary", RGB::new(37, 99, 235));
+/// p.add_named("danger", RGB::new(220, 38, 38));
+/// let (i, _) = p.nearest(&RGB::new(240, 20, 30), Metric::CIEDE2000).unwrap();
+/// assert_eq!(p.get_name(i), Some("danger"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Palette
+{
+    entries: Vec<(Option<String>, RGB)>,
+    labs: Vec<Lab>,
+    max_chroma: f64,
+    tree: OnceLock<Vec<usize>>
+}
+
+// A nearest color query, the factors
+// are the ones of cie76_factors
+struct Query
+{
+    lab: Lab,
+    metric: Metric,
+    factors: (f64, f64),
+    k: usize
+}
+
+impl PartialEq for Palette
+{
+    fn eq(&self, other: &Palette) -> bool
+    {
+        self.entries == other.entries
+    }
+}
+
+impl Palette
+{
+    /// Makes a new empty palette.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Palette;
+    /// let p = Palette::new();
+    /// assert!(p.is_empty());
+    /// ```
+    pub fn new() -> Palette
+    {
+        Palette::default()
+    }
+
+    /// Makes a palette from colors without names.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, Colormap};
+    /// let p = Palette::from_colors(&Colormap::Viridis.take(16));
+    /// assert_eq!(p.len(), 16);
+    /// ```
+    pub fn from_colors(colors: &[RGB]) -> Palette
+    {
+        let mut palette = Palette::new();
+
+        for c in colors.iter()
+        {
+            palette.add(*c);
+        }
+
+        palette
+    }
+
+    /// Adds a color without a name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, RGB};
+    /// let mut p = Palette::new();
+    /// p.add(RGB::new(0, 0, 0));
+    /// ```
+    pub fn add(&mut self, color: RGB)
+    {
+        self.push(None, color);
+    }
+
+    /// Adds a color with a name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, RGB};
+    /// let mut p = Palette::new();
+    /// p.add_named("background", RGB::new(255, 255, 255));
+    /// ```
+    pub fn add_named(&mut self, name: &str, color: RGB)
+    {
+        self.push(Some(name.to_string()), color);
+    }
+
+    /// Gets the number of colors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, RGB};
+    /// let p = Palette::from_colors(&[RGB::new(0, 0, 0), RGB::new(0, 0, 0)]);
+    /// assert_eq!(p.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize
+    {
+        self.entries.len()
+    }
+
+    /// Checks if there are no colors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::Palette;
+    /// assert!(Palette::new().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool
+    {
+        self.entries.is_empty()
+    }
+
+    /// Gets the color at an index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, RGB};
+    /// let p = Palette::from_colors(&[RGB::new(1, 2, 3)]);
+    /// assert_eq!(p.get_color(0), Some(RGB::new(1, 2, 3)));
+    /// assert_eq!(p.get_color(1), None);
+    /// ```
+    pub fn get_color(&self, index: usize) -> Option<RGB>
+    {
+        self.entries.get(index).map(|(_, c)| *c)
+    }
+
+    /// Gets the name of the color at an index,
+    /// None if it has no name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, RGB};
+    /// let mut p = Palette::new();
+    /// p.add_named("accent", RGB::new(255, 0, 128));
+    /// assert_eq!(p.get_name(0), Some("accent"));
+    /// ```
+    pub fn get_name(&self, index: usize) -> Option<&str>
+    {
+        self.entries.get(index).and_then(|(n, _)| n.as_deref())
+    }
+
+    /// Gets the index of the first color with a name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, RGB};
+    /// let mut p = Palette::new();
+    /// p.add_named("accent", RGB::new(255, 0, 128));
+    /// assert_eq!(p.find_name("accent"), Some(0));
+    /// ```
+    pub fn find_name(&self, name: &str) -> Option<usize>
+    {
+        self.entries.iter().position(|(n, _)| n.as_deref() == Some(name))
+    }
+
+    /// Gets all the colors in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, RGB};
+    /// let p = Palette::from_colors(&[RGB::new(1, 2, 3)]);
+    /// assert_eq!(p.get_colors(), vec![RGB::new(1, 2, 3)]);
+    /// ```
+    pub fn get_colors(&self) -> Vec<RGB>
+    {
+        self.entries.iter().map(|(_, c)| *c).collect()
+    }
+
+    /// Finds the closest color to another one.
+    ///
+    /// Returns its index and the difference,
+    /// or None if the palette is empty.
+    ///
+    /// The color is the reference of
+    /// the metric, see the Metric definition.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, Metric, RGB};
+    /// let p = Palette::from_colors(&[RGB::new(0, 0, 0), RGB::new(255, 255, 255)]);
+    /// let (i, distance) = p.nearest(&RGB::new(200, 200, 200), Metric::CIEDE2000).unwrap();
+    /// assert_eq!(i, 1);
+    /// ```
+    pub fn nearest(&self, color: &RGB, metric: Metric) -> Option<(usize, f64)>
+    {
+        self.nearest_k(color, 1, metric).pop()
+    }
+
+    /// Finds the k closest colors to another one,
+    /// sorted from the closest.
+    ///
+    /// Returns their indexes and differences,
+    /// fewer if the palette is smaller than k.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, Metric, Colormap, RGB};
+    /// let p = Palette::from_colors(&Colormap::Magma.take(32));
+    /// let matches = p.nearest_k(&RGB::new(180, 50, 120), 3, Metric::CIEDE2000);
+    /// assert_eq!(matches.len(), 3);
+    /// ```
+    pub fn nearest_k(&self, color: &RGB, k: usize, metric: Metric) -> Vec<(usize, f64)>
+    {
+        let mut best = Vec::with_capacity(k.min(self.len()) + 1);
+        if k == 0 {return best}
+
+        let lab = Lab::from(*color);
+        let factors = cie76_factors(&lab, metric, self.max_chroma);
+        let query = Query {lab, metric, factors, k};
+        let tree = self.tree.get_or_init(|| build_tree(&self.labs));
+        self.search(tree, 0, &query, &mut best);
+        best
+    }
+
+    /// Replaces each color with
+    /// the closest one in the palette.
+    ///
+    /// If the palette is empty the
+    /// colors are returned as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, Metric, RGB};
+    /// let p = Palette::from_colors(&[RGB::new(0, 0, 0), RGB::new(255, 255, 255)]);
+    /// let colors = p.snap_all(&[RGB::new(20, 20, 20), RGB::new(230, 240, 250)], Metric::CIEDE2000);
+    /// assert_eq!(colors, vec![RGB::new(0, 0, 0), RGB::new(255, 255, 255)]);
+    /// ```
+    pub fn snap_all(&self, colors: &[RGB], metric: Metric) -> Vec<RGB>
+    {
+        colors.iter().map(|c|
+        {
+            self.nearest(c, metric).map(|(i, _)| self.entries[i].1).unwrap_or(*c)
+        }).collect()
+    }
+
+    /// Checks if the palette has a color with
+    /// a CIEDE2000 difference of at most
+    /// the threshold from another one.
+    ///
+    /// A threshold of 1 finds colors that
+    /// look the same, 2 or 3 finds colors
+    /// that look the same at a glance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Palette, RGB};
+    /// let p = Palette::from_colors(&[RGB::new(37, 99, 235)]);
+    /// assert!(p.contains_close(&RGB::new(38, 100, 233), 1.0));
+    /// assert!(!p.contains_close(&RGB::new(220, 38, 38), 1.0));
+    /// ```
+    pub fn contains_close(&self, color: &RGB, threshold: f64) -> bool
+    {
+        self.nearest(color, Metric::CIEDE2000).is_some_and(|(_, d)| d <= threshold)
+    }
+
+    // Adds an entry and drops the tree
+    fn push(&mut self, name: Option<String>, color: RGB)
+    {
+        let lab = Lab::from(color);
+        self.max_chroma = self.max_chroma.max(lab.get_a().hypot(lab.get_b()));
+        self.entries.push((name, color));
+        self.labs.push(lab);
+        self.tree = OnceLock::new();
+    }
+
+    // Searches a subtree keeping the k best
+    // matches sorted by difference and index.
+    // The far side is skipped when its distance
+    // on the axis / factor can't beat the worst match
+    fn search(&self, tree: &[usize], depth: usize, query: &Query, best: &mut Vec<(usize, f64)>)
+    {
+        if tree.is_empty() {return}
+        let middle = tree.len() / 2;
+        let i = tree[middle];
+        let d = delta_e(&query.lab, &self.labs[i], query.metric);
+        let position = best.iter().position(|(j, e)| d < *e || (d == *e && i < *j)).unwrap_or(best.len());
+
+        if position < query.k
+        {
+            best.insert(position, (i, d));
+            best.truncate(query.k);
+        }
+
+        let offset = axis(&query.lab, depth) - axis(&self.labs[i], depth);
+        let (left, right) = (&tree[..middle], &tree[middle + 1..]);
+        let (near, far) = if offset < 0.0 {(left, right)} else {(right, left)};
+        self.search(near, depth + 1, query, best);
+
+        let factor = match depth % 3 {0 => query.factors.0, _ => query.factors.1};
+
+        if best.len() < query.k || offset.abs() / factor <= best[query.k - 1].1
+        {
+            self.search(far, depth + 1, query, best);
+        }
+    }
+}
+
+// Builds an implicit k-d tree, each subtree
+// has its splitting color in the middle
+fn build_tree(labs: &[Lab]) -> Vec<usize>
+{
+    let mut tree: Vec<usize> = (0..labs.len()).collect();
+    split(labs, &mut tree, 0);
+    tree
+}
+
+fn split(labs: &[Lab], tree: &mut [usize], depth: usize)
+{
+    if tree.len() <= 1 {return}
+    let middle = tree.len() / 2;
+    tree.select_nth_unstable_by(middle, |a, b| axis(&labs[*a], depth).total_cmp(&axis(&labs[*b], depth)));
+    let (left, right) = tree.split_at_mut(middle);
+    split(labs, left, depth + 1);
+    split(labs, &mut right[1..], depth + 1);
+}
+
+// Gets the Lab value used to split at a depth
+fn axis(lab: &Lab, depth: usize) -> f64
+{
+    match depth % 3
+    {
+        0 => lab.get_l(),
+        1 => lab.get_a(),
+        _ => lab.get_b()
+    }
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    // Compares every color, for checking the tree
+    fn brute_force(palette: &Palette, color: &RGB, k: usize, metric: Metric) -> Vec<(usize, f64)>
+    {
+        let query = Lab::from(*color);
+        let mut all: Vec<(usize, f64)> = palette.labs.iter().enumerate().map(|(i, lab)| (i, delta_e(&query, lab, metric))).collect();
+        all.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        all.truncate(k);
+        all
+    }
+
+    #[test]
+    fn palette_test()
+    {
+        let mut p = Palette::new();
+        assert_eq!(p.nearest(&RGB::new(0, 0, 0), Metric::CIE76), None);
+        assert!(!p.contains_close(&RGB::new(0, 0, 0), 100.0));
+        assert_eq!(p.snap_all(&[RGB::new(1, 2, 3)], Metric::CIE76), vec![RGB::new(1, 2, 3)]);
+
+        p.add_named("red", RGB::new(255, 0, 0));
+        p.add(RGB::new(0, 128, 0));
+        p.add_named("blue", RGB::new(0, 0, 255));
+        p.add_named("red2", RGB::new(255, 0, 0));
+        assert_eq!(p.len(), 4);
+        assert_eq!(p.get_name(1), None);
+        assert_eq!(p.get_name(2), Some("blue"));
+        assert_eq!(p.find_name("red2"), Some(3));
+        assert_eq!(p.find_name("green"), None);
+
+        // Ties go to the first color
+        assert_eq!(p.nearest(&RGB::new(255, 0, 0), Metric::CIEDE2000), Some((0, 0.0)));
+        let matches = p.nearest_k(&RGB::new(250, 10, 10), 3, Metric::CIE76);
+        assert_eq!(matches.iter().map(|m| m.0).collect::<Vec<usize>>(), vec![0, 3, 1]);
+        assert_eq!(p.nearest_k(&RGB::new(0, 0, 0), 10, Metric::CIE76).len(), 4);
+        assert_eq!(p.nearest_k(&RGB::new(0, 0, 0), 0, Metric::CIE76), vec![]);
+
+        assert_eq!(p.snap_all(&[RGB::new(10, 20, 200), RGB::new(20, 100, 30)], Metric::CIEDE2000),
+            vec![RGB::new(0, 0, 255), RGB::new(0, 128, 0)]);
+
+        assert!(p.contains_close(&RGB::new(0, 0, 254), 1.0));
+        assert!(!p.contains_close(&RGB::new(255, 255, 0), 10.0));
+
+        // The tree is rebuilt after adding
+        p.add(RGB::new(255, 255, 0));
+        assert_eq!(p.nearest(&RGB::new(250, 250, 0), Metric::CIEDE2000).unwrap().0, 4);
+        assert_eq!(p, p.clone());
+    }
+
+    #[test]
+    fn tree_test()
+    {
+        let colors: Vec<RGB> = (0..1500).map(|i|
+        {
+            let n = (i as u32).wrapping_mul(2654435761);
+            RGB::new((n >> 24) as u8, (n >> 12) as u8, (n >> 4) as u8)
+        }).collect();
+
+        let p = Palette::from_colors(&colors);
+        let metrics = [Metric::CIE76, Metric::CIE94GraphicArts, Metric::CIE94Textiles,
+            Metric::CIEDE2000, Metric::CMC(2.0, 1.0), Metric::CMC(1.0, 1.0)];
+
+        for i in 0..60u32
+        {
+            let n = i.wrapping_mul(40503).wrapping_add(977);
+            let color = RGB::new((n * 7) as u8, (n >> 3) as u8, ((n * 13) >> 2) as u8);
+
+            for metric in metrics.iter()
+            {
+                assert_eq!(p.nearest_k(&color, 5, *metric), brute_force(&p, &color, 5, *metric));
+            }
+        }
+    }
+}
@@ -0,0 +1,1166 @@
+use crate::*;
+use std::ops::Range;
+use crate::mix::mix_rgba;
+
+// The default percentage of a step
+// and the highest step, like "darker5"
+const STEP_SIZE: f64 = 10.0;
+const MAX_STEPS: u32 = 5;
+
+/// Options that change how color
+/// strings are parsed.
+/// 
+/// The default options give the
+/// same results as parse_color.
+/// 
+/// # Example
+/// 
+/// ```
+/// use colorskill::{ParseOptions, LightnessModel};
+/// let options = ParseOptions {lightness: LightnessModel::OKLCh, ..Default::default()};
+///
+/// // Steps of 5% up to "darker20"
+/// let options = ParseOptions {step_size: 5.0, max_steps: 20, ..Default::default()};
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParseOptions
+{
+    /// The color space used by the
+    /// darker and lighter keywords.
+    pub lightness: LightnessModel,
+
+    /// The percentage of one step of the
+    /// darker, lighter, saturate and desaturate
+    /// keywords, so "darker3" is 3 steps.
+    /// The default is 10.
+    pub step_size: f64,
+
+    /// The highest step number,
+    /// with the default of 5 "darker5"
+    /// works but "darker6" doesn't.
+    pub max_steps: u32
+}
+
+impl Default for ParseOptions
+{
+    fn default() -> ParseOptions
+    {
+        ParseOptions
+        {
+            lightness: LightnessModel::HSL, step_size: STEP_SIZE, max_steps: MAX_STEPS
+        }
+    }
+}
+
+/// Parses a color string strictly.
+///
+/// Accepts the same inputs as parse_color
+/// but returns an error instead of guessing.
+///
+/// Invalid components are not turned into 0
+/// and unknown names do not return the reference.
+///
+/// It also understands the CSS functional notations
+/// "rgb()", "rgba()", "hsl()", "hsla()" and "hwb()",
+/// and the non standard "hsv()" (or "hsb()"),
+/// in both comma and space separated forms,
+/// with percentages, hue units and a "/ alpha" slash.
+///
+/// Colors can be mixed with the CSS "color-mix()" function,
+/// like "color-mix(in oklch, red 40%, blue)".
+/// The spaces are srgb, srgb-linear, hsl, lab, lch,
+/// oklab and oklch, and the polar ones can take
+/// a "shorter", "longer", "increasing" or
+/// "decreasing hue" method.
+///
+/// A color can be followed by modifiers that
+/// are applied from left to right, optionally
+/// separated by a "|", like "steelblue darker2 rotate(30)"
+/// or "#336699 | lighter 15% | alpha 0.5".
+/// Modifiers without a color change the reference.
+///
+/// ```text
+/// expression = [color] {["|"] modifier}
+/// modifier   = name [amount] | name "(" amount ")" | name ":" amount
+/// ```
+///
+/// The modifiers are:
+///
+/// "darker", "lighter", "saturate" and "desaturate"
+/// with an optional amount that is one step by default,
+/// and their numbered forms like "darker3" that don't take one,
+///
+/// "rotate" with a hue in degrees or with a unit,
+///
+/// "alpha" with a value from 0 to 1 or a percentage,
+///
+/// and "grayscale", "invert" and "complement".
+///
+/// The amounts of the first four are percentages,
+/// with or without a "%" and after any separator,
+/// so "darker 37", "darker:37", "darker(37)" and
+/// "darker 37%" are the same, and "darker 0.5" is
+/// half a percent. Write fractions as percentages,
+/// like "lighter 20%" for a fifth.
+/// A step is 10% by default,
+/// check ParseOptions to change it.
+///
+/// Alpha values are checked but ignored,
+/// use try_parse_rgba to get them.
+///
+/// Out of range values are errors instead of being clamped.
+///
+/// The error carries the byte span of the
+/// offending token in the input string.
+///
+/// # Examples
+///
+/// ```
+/// use colorskill::{try_parse_color, ColorError};
+/// let c = try_parse_color("34,65,39", (0, 0, 0));
+/// assert_eq!(c, Ok((34, 65, 39)));
+///
+/// let err = try_parse_color("300,12,9", (0, 0, 0)).unwrap_err();
+/// assert_eq!(err.span(), 0..3);
+///
+/// let c = try_parse_color("red | rotate 120deg", (0, 0, 0));
+/// assert_eq!(c, Ok((0, 255, 0)));
+///
+/// let err = try_parse_color("red darker 150%", (0, 0, 0)).unwrap_err();
+/// assert_eq!(err.span(), 11..15);
+/// ```
+pub fn try_parse_color(s: &str, reference: (u8, u8, u8)) -> Result<(u8, u8, u8), ColorError>
+{
+    try_parse_color_with(s, reference, &ParseOptions::default())
+}
+
+/// Parses a color string strictly with options.
+///
+/// Works like try_parse_color, but the options
+/// can change how keywords like "darker" work.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{try_parse_color_with, ParseOptions, LightnessModel};
+/// let options = ParseOptions {lightness: LightnessModel::OKLCh, ..Default::default()};
+/// let c = try_parse_color_with("lighter", (10, 34, 50), &options);
+/// ```
+pub fn try_parse_color_with(s: &str, reference: (u8, u8, u8), options: &ParseOptions)
+    -> Result<(u8, u8, u8), ColorError>
+{
+    let c = try_parse_rgba_with(s, (reference.0, reference.1, reference.2, 255), options)?;
+    Ok((c.0, c.1, c.2))
+}
+
+/// Parses a color string with alpha strictly.
+///
+/// Works like try_parse_color but returns
+/// an RGBA tuple with the alpha of hex values
+/// like "#rrggbbaa" and functions like "rgba()".
+///
+/// Colors without alpha are opaque (255),
+/// "transparent" has an alpha of 0, and
+/// keywords like darker and saturate keep the reference alpha.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::try_parse_rgba;
+/// let c = try_parse_rgba("rgb(255 0 0 / 50%)", (0, 0, 0, 255));
+/// assert_eq!(c, Ok((255, 0, 0, 128)));
+/// ```
+pub fn try_parse_rgba(s: &str, reference: (u8, u8, u8, u8)) -> Result<(u8, u8, u8, u8), ColorError>
+{
+    try_parse_rgba_with(s, reference, &ParseOptions::default())
+}
+
+/// Parses a color string with alpha strictly with options.
+///
+/// Works like try_parse_rgba, but the options
+/// can change how keywords like "darker" work.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{try_parse_rgba_with, ParseOptions, LightnessModel};
+/// let options = ParseOptions {lightness: LightnessModel::OKLCh, ..Default::default()};
+/// let c = try_parse_rgba_with("lighter", (10, 34, 50, 128), &options);
+/// ```
+pub fn try_parse_rgba_with(s: &str, reference: (u8, u8, u8, u8), options: &ParseOptions)
+    -> Result<(u8, u8, u8, u8), ColorError>
+{
+    parse_rgba_at(s, 0, reference, options)
+}
+
+// Parses a color expression with alpha,
+// a base color followed by modifiers.
+// offset is the position of the string in the input
+#[allow(clippy::suspicious_else_formatting)]
+fn parse_rgba_at(s: &str, offset: usize, reference: (u8, u8, u8, u8), options: &ParseOptions)
+    -> Result<(u8, u8, u8, u8), ColorError>
+{
+    let words = split_words(s, offset);
+    let first = words.iter().position(|w| w.0 == "|" || is_modifier(w.0, options)).unwrap_or(words.len());
+
+    let mut color = if first == 0 && !words.is_empty()
+    {
+        if words[0].0 == "|" {return Err(syntax_error("expected a color", words[0].1.clone()))}
+        reference
+    }
+
+    else
+    {
+        parse_base_words(s, offset, &words[..first], reference, options)?
+    };
+
+    let mut i = first;
+
+    while i < words.len()
+    {
+        let (word, span) = (words[i].0, words[i].1.clone());
+        i += 1;
+
+        if word == "|"
+        {
+            match words.get(i)
+            {
+                Some((next, _)) if is_modifier(next, options) => continue,
+                Some((_, nspan)) => return Err(syntax_error("expected a modifier", nspan.clone())),
+                None => return Err(syntax_error("expected a modifier", span.end..span.end))
+            }
+        }
+
+        let (name, mut amount) = split_modifier(word, span.clone())?;
+
+        let (modifier, default, takes_amount) = match lookup_modifier(name, options)
+        {
+            Some(m) => m,
+            None if starts_like_number(word) => return Err(syntax_error("unexpected amount", span)),
+            None => return Err(syntax_error("unknown modifier", span))
+        };
+
+        // The amount can also be the next word
+        if let (None, Some((next, nspan))) = (&amount, words.get(i))
+        {
+            let free = *next != "|" && !is_modifier(next, options);
+
+            if takes_amount && free && (default.is_none() || starts_like_number(next))
+            {
+                amount = Some((next, nspan.clone()));
+                i += 1;
+            }
+        }
+
+        if let (false, Some((_, aspan))) = (takes_amount, &amount)
+        {
+            return Err(syntax_error("unexpected amount", aspan.clone()))
+        }
+
+        color = apply_modifier(modifier, span, amount, default, color, options)?;
+    }
+
+    Ok(color)
+}
+
+// Parses the words of a base color.
+// If the words don't make a color but the first ones do,
+// the next word is reported as an unknown modifier
+// or an unexpected amount.
+fn parse_base_words(s: &str, offset: usize, words: &[Arg], reference: (u8, u8, u8, u8), options: &ParseOptions)
+    -> Result<(u8, u8, u8, u8), ColorError>
+{
+    let slice = |n: usize|
+    {
+        let (start, end) = (words[0].1.start, words[n - 1].1.end);
+        (&s[start - offset..end - offset], start)
+    };
+
+    if words.is_empty() {return parse_base_at(s, offset, reference, options)}
+    let (base, start) = slice(words.len());
+    let err = match parse_base_at(base, start, reference, options) {Ok(c) => return Ok(c), Err(e) => e};
+
+    for n in (1..words.len()).rev()
+    {
+        let (base, start) = slice(n);
+
+        if parse_base_at(base, start, reference, options).is_ok()
+        {
+            let message = if starts_like_number(words[n].0) {"unexpected amount"} else {"unknown modifier"};
+            return Err(syntax_error(message, words[n].1.clone()))
+        }
+    }
+
+    Err(err)
+}
+
+// Splits an expression into words outside of parentheses.
+// Whitespace next to a comma doesn't split,
+// so "1, 2, 3" is one word, and a "|" is a word on its own.
+fn split_words(s: &str, offset: usize) -> Vec<Arg<'_>>
+{
+    let next_to_comma = |i: usize|
+    {
+        s[..i].trim_end().ends_with(',') || s[i..].trim_start().starts_with(',')
+    };
+
+    let mut words: Vec<Arg> = Vec::new();
+    let (mut depth, mut start) = (0, None);
+
+    for (i, c) in s.char_indices()
+    {
+        if depth == 0 && (c == '|' || c.is_whitespace() && !next_to_comma(i))
+        {
+            if let Some(j) = start.take() {words.push((&s[j..i], offset + j..offset + i))}
+            if c == '|' {words.push((&s[i..i + 1], offset + i..offset + i + 1))}
+            continue
+        }
+
+        match c
+        {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            _ => {}
+        }
+
+        if start.is_none() {start = Some(i)}
+    }
+
+    if let Some(j) = start {words.push((&s[j..], offset + j..offset + s.len()))}
+    words
+}
+
+// A modifier in a color expression
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Modifier
+{
+    Lightness(bool),
+    Saturation(bool),
+    Rotate,
+    Alpha,
+    Grayscale,
+    Invert,
+    Complement
+}
+
+// Gets a modifier by name with its default amount
+// and whether it takes an amount.
+// Modifiers without a default need an amount.
+fn lookup_modifier(name: &str, options: &ParseOptions) -> Option<(Modifier, Option<f64>, bool)>
+{
+    let name = name.to_lowercase();
+
+    let steps =
+    [
+        ("darker", Modifier::Lightness(true)),
+        ("lighter", Modifier::Lightness(false)),
+        ("saturate", Modifier::Saturation(false)),
+        ("desaturate", Modifier::Saturation(true))
+    ];
+
+    for (prefix, modifier) in steps.iter()
+    {
+        if let Some(n) = name.strip_prefix(prefix)
+        {
+            if n.is_empty() {return Some((*modifier, Some(options.step_size), true))}
+            if !n.chars().all(|c| c.is_ascii_digit()) {return None}
+
+            // Numbered steps have a fixed amount
+            return match n.parse::<u32>()
+            {
+                Ok(steps) if (1..=options.max_steps).contains(&steps) =>
+                    Some((*modifier, Some(f64::from(steps) * options.step_size), false)),
+                _ => None
+            }
+        }
+    }
+
+    match &name[..]
+    {
+        "rotate" => Some((Modifier::Rotate, None, true)),
+        "alpha" => Some((Modifier::Alpha, None, true)),
+        "grayscale" | "greyscale" => Some((Modifier::Grayscale, Some(0.0), false)),
+        "invert" => Some((Modifier::Invert, Some(0.0), false)),
+        "complement" => Some((Modifier::Complement, Some(0.0), false)),
+        _ => None
+    }
+}
+
+// Checks if a word is a modifier
+fn is_modifier(word: &str, options: &ParseOptions) -> bool
+{
+    let name = word.split(['(', ':']).next().unwrap_or("");
+    lookup_modifier(name, options).is_some()
+}
+
+// Checks if a word looks like an amount
+fn starts_like_number(word: &str) -> bool
+{
+    word.starts_with(|c: char| c.is_ascii_digit() || "+-.".contains(c))
+}
+
+// Splits a modifier like "rotate(30)" or "darker:37"
+// into its name and the amount
+fn split_modifier(word: &str, span: Range<usize>) -> Result<(&str, Option<Arg<'_>>), ColorError>
+{
+    let open = match word.find(['(', ':'])
+    {
+        Some(open) => open,
+        None => return Ok((word, None))
+    };
+
+    let colon = word[open..].starts_with(':');
+
+    if !colon && !word.ends_with(')')
+    {
+        return Err(syntax_error("expected a closing parenthesis", span.end..span.end))
+    }
+
+    let inner = if colon {&word[open + 1..]} else {&word[open + 1..word.len() - 1]};
+    let amount = trim_span(inner, span.start + open + 1);
+    if amount.0.is_empty() {return Err(syntax_error("expected an amount", amount.1))}
+    Ok((&word[..open], Some(amount)))
+}
+
+// Applies a modifier to a color.
+// span is the position of the modifier
+fn apply_modifier(modifier: Modifier, span: Range<usize>, amount: Option<Arg>, default: Option<f64>,
+    c: (u8, u8, u8, u8), options: &ParseOptions) -> Result<(u8, u8, u8, u8), ColorError>
+{
+    let rgb = (c.0, c.1, c.2);
+    let with_alpha = |t: (u8, u8, u8)| Ok((t.0, t.1, t.2, c.3));
+
+    let amount = match (amount, default)
+    {
+        (Some(a), _) => a,
+        (None, Some(d)) => return apply_percentage(modifier, d, c, options),
+        (None, None) => return Err(syntax_error("expected an amount", span.end..span.end))
+    };
+
+    let (a, aspan) = amount;
+
+    match modifier
+    {
+        Modifier::Rotate => with_alpha(rotate_color_hue(rgb, parse_hue(a, aspan)?)),
+        Modifier::Alpha => Ok((c.0, c.1, c.2, alpha_to_u8(parse_alpha(a, aspan)?))),
+        _ =>
+        {
+            let (n, _) = parse_number(a, aspan.clone())?;
+            if !(0.0..=100.0).contains(&n) {return Err(out_of_range(a, aspan))}
+            apply_percentage(modifier, n, c, options)
+        }
+    }
+}
+
+// Applies a modifier with a percentage amount,
+// or without an amount
+fn apply_percentage(modifier: Modifier, amount: f64, c: (u8, u8, u8, u8), options: &ParseOptions)
+    -> Result<(u8, u8, u8, u8), ColorError>
+{
+    let rgb = (c.0, c.1, c.2);
+
+    let t = match modifier
+    {
+        Modifier::Lightness(darker) => change_color_lightness_with(rgb, darker, amount, options.lightness),
+        Modifier::Saturation(desaturate) => change_color_saturation(rgb, desaturate, amount),
+        Modifier::Rotate => rotate_color_hue(rgb, amount),
+        Modifier::Alpha => return Ok((c.0, c.1, c.2, alpha_to_u8(amount / 100.0))),
+        Modifier::Grayscale => grayscale_color(rgb),
+        Modifier::Invert => invert_color(rgb),
+        Modifier::Complement => complement_color(rgb)
+    };
+
+    Ok((t.0, t.1, t.2, c.3))
+}
+
+// Parses a base color with alpha
+// offset is the position of the string in the input
+#[allow(clippy::suspicious_else_formatting)]
+fn parse_base_at(s: &str, offset: usize, reference: (u8, u8, u8, u8), options: &ParseOptions)
+    -> Result<(u8, u8, u8, u8), ColorError>
+{
+    let opaque = |c: (u8, u8, u8)| Ok((c.0, c.1, c.2, 255));
+    let (token, span) = trim_span(s, offset);
+
+    if token.is_empty()
+    {
+        return Err(syntax_error("empty color string", span))
+    }
+
+    let cs = clean_string(token);
+
+    match &cs[..]
+    {
+        "random" => opaque(random_color()),
+        "transparent" => Ok((0, 0, 0, 0)),
+        _ =>
+        {
+            if cs.starts_with("color-mix(")
+            {
+                parse_color_mix(token, span, reference, options)
+            }
+
+            else if token.contains('(')
+            {
+                parse_function(token, span)
+            }
+
+            else if token.contains(',')
+            {
+                opaque(parse_triple(token, span.start)?)
+            }
+
+            else if cs.starts_with('#') || cs.starts_with("0x")
+            {
+                match parse_hex(&cs)
+                {
+                    Some(h) => Ok(h),
+                    None => Err(syntax_error("invalid hex value", span))
+                }
+            }
+
+            else if let Some(c) = lookup_color_name(&cs)
+            {
+                opaque(c)
+            }
+
+            else if let Some(h) = parse_hex(&cs)
+            {
+                Ok(h)
+            }
+
+            else if token.chars().all(|c| c.is_ascii_alphabetic() || c.is_whitespace())
+            {
+                Err(ColorError::UnknownName {name: token.to_string(), span})
+            }
+
+            else
+            {
+                Err(syntax_error("unrecognized color", span))
+            }
+        }
+    }
+}
+
+// A function argument with its span
+type Arg<'a> = (&'a str, Range<usize>);
+
+// A color mix color with its optional percentage
+type MixColor = ((u8, u8, u8, u8), Option<f64>);
+
+// Parses a CSS functional notation like "rgb(1 2 3 / 50%)"
+fn parse_function(token: &str, span: Range<usize>) -> Result<(u8, u8, u8, u8), ColorError>
+{
+    let (name, args, alpha) = split_function(token, span.clone())?;
+
+    let alpha = match alpha
+    {
+        Some((a, aspan)) => alpha_to_u8(parse_alpha(a, aspan)?),
+        None => 255
+    };
+
+    let with_alpha = |c: (u8, u8, u8)| Ok((c.0, c.1, c.2, alpha));
+
+    match &name.to_lowercase()[..]
+    {
+        "rgb" | "rgba" =>
+        {
+            check_count(&args, 3, &span)?;
+            let mut v = [0.0; 3];
+
+            for (i, (arg, aspan)) in args.into_iter().enumerate()
+            {
+                let (n, percent) = parse_number(arg, aspan.clone())?;
+                let n = if percent {n / 100.0} else {n / 255.0};
+                if !(0.0..=1.0).contains(&n) {return Err(out_of_range(arg, aspan))}
+                v[i] = n;
+            }
+
+            with_alpha(unit_rgb_to_tuple((v[0], v[1], v[2])))
+        },
+        "hsl" | "hsla" | "hsv" | "hsva" | "hsb" | "hsba" | "hwb" =>
+        {
+            check_count(&args, 3, &span)?;
+            let hue = parse_hue(args[0].0, args[0].1.clone())?;
+            let x = parse_percentage(args[1].0, args[1].1.clone())?;
+            let y = parse_percentage(args[2].0, args[2].1.clone())?;
+
+            match &name.to_lowercase()[..]
+            {
+                "hwb" => with_alpha(unit_rgb_to_tuple(hwb_to_unit_rgb(hue, x, y))),
+                "hsl" | "hsla" => with_alpha(unit_rgb_to_tuple(hsl_to_unit_rgb(hue, x, y))),
+                _ => with_alpha(unit_rgb_to_tuple(hsv_to_unit_rgb(hue, x, y)))
+            }
+        },
+        _ =>
+        {
+            let (_, nspan) = trim_span(name, span.start);
+            Err(syntax_error("unknown function", nspan))
+        }
+    }
+}
+
+// Parses a CSS color mix like "color-mix(in oklch, red 40%, blue)".
+// The colors can be any color string, even another color-mix.
+fn parse_color_mix(token: &str, span: Range<usize>, reference: (u8, u8, u8, u8), options: &ParseOptions)
+    -> Result<(u8, u8, u8, u8), ColorError>
+{
+    if !token.ends_with(')')
+    {
+        return Err(syntax_error("expected a closing parenthesis", span.end..span.end))
+    }
+
+    let open = token.find('(').unwrap();
+    let inner = &token[open + 1..token.len() - 1];
+    let offset = span.start + open + 1;
+
+    // Split on the commas outside of nested functions
+    let mut parts: Vec<Arg> = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+
+    for (i, c) in inner.char_indices()
+    {
+        match c
+        {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Err(syntax_error("unexpected parenthesis", offset + i..offset + i + 1)),
+            ')' => depth -= 1,
+            ',' if depth == 0 =>
+            {
+                parts.push(trim_span(&inner[start..i], offset + start));
+                start = i + 1;
+            },
+            _ => {}
+        }
+    }
+
+    parts.push(trim_span(&inner[start..], offset + start));
+    check_count(&parts, 3, &span)?;
+    let (space, hue) = parse_mix_method(parts[0].0, parts[0].1.clone())?;
+    let (a, pa) = parse_mix_color(&parts[1], reference, options)?;
+    let (b, pb) = parse_mix_color(&parts[2], reference, options)?;
+
+    // Missing percentages add up to 100%
+    let (pa, pb) = match (pa, pb)
+    {
+        (None, None) => (0.5, 0.5),
+        (Some(p), None) => (p, 1.0 - p),
+        (None, Some(p)) => (1.0 - p, p),
+        (Some(p1), Some(p2)) => (p1, p2)
+    };
+
+    let sum = pa + pb;
+
+    if sum <= 0.0
+    {
+        return Err(syntax_error("the percentages can't add up to 0", span))
+    }
+
+    // A sum below 100% makes the result transparent
+    let c = mix_rgba(a, b, pb / sum, space, hue);
+    let alpha = f64::from(c.3) / 255.0 * sum.min(1.0);
+    Ok((c.0, c.1, c.2, alpha_to_u8(alpha)))
+}
+
+// Parses the "in <space> [<method> hue]" part of a color mix
+fn parse_mix_method(arg: &str, span: Range<usize>) -> Result<(ColorSpace, HueInterpolation), ColorError>
+{
+    let words: Vec<String> = arg.split_whitespace().map(|w| w.to_lowercase()).collect();
+
+    if words.first().map(|w| &w[..]) != Some("in") || words.len() < 2
+    {
+        return Err(syntax_error("expected \"in\" and a color space", span))
+    }
+
+    let space = match &words[1][..]
+    {
+        "srgb" => ColorSpace::SRGB,
+        "srgb-linear" => ColorSpace::LinearSRGB,
+        "hsl" => ColorSpace::HSL,
+        "lab" => ColorSpace::Lab,
+        "lch" => ColorSpace::LCh,
+        "oklab" => ColorSpace::OKLab,
+        "oklch" => ColorSpace::OKLCh,
+        _ => return Err(syntax_error("unknown color space", span))
+    };
+
+    if words.len() == 2 {return Ok((space, HueInterpolation::Shorter))}
+    let polar = matches!(space, ColorSpace::HSL | ColorSpace::LCh | ColorSpace::OKLCh);
+
+    if !polar || words.len() != 4 || words[3] != "hue"
+    {
+        return Err(syntax_error("unexpected hue interpolation method", span))
+    }
+
+    match &words[2][..]
+    {
+        "shorter" => Ok((space, HueInterpolation::Shorter)),
+        "longer" => Ok((space, HueInterpolation::Longer)),
+        "increasing" => Ok((space, HueInterpolation::Increasing)),
+        "decreasing" => Ok((space, HueInterpolation::Decreasing)),
+        _ => Err(syntax_error("unknown hue interpolation method", span))
+    }
+}
+
+// Parses a "<color> [<percentage>]" part of a color mix,
+// the percentage can also come before the color
+#[allow(clippy::suspicious_else_formatting)]
+fn parse_mix_color(arg: &Arg, reference: (u8, u8, u8, u8), options: &ParseOptions)
+    -> Result<MixColor, ColorError>
+{
+    let (s, span) = (arg.0, arg.1.clone());
+    let first = s.split_whitespace().next().unwrap_or("");
+    let last = s.split_whitespace().last().unwrap_or("");
+
+    let percentage = |p: &str, start: usize|
+    {
+        let pspan = start..start + p.len();
+        let (n, _) = parse_number(p, pspan.clone())?;
+        if !(0.0..=100.0).contains(&n) {return Err(out_of_range(p, pspan))}
+        Ok(n / 100.0)
+    };
+
+    if first.ends_with('%') && first.len() < s.len()
+    {
+        let p = percentage(first, span.start)?;
+        let rest = &s[first.len()..];
+        Ok((parse_rgba_at(rest, sub_offset(s, rest, span.start), reference, options)?, Some(p)))
+    }
+
+    else if last.ends_with('%') && last.len() < s.len() && !last.contains(')')
+    {
+        let rest = &s[..s.len() - last.len()];
+        let p = percentage(last, span.start + rest.len())?;
+        Ok((parse_rgba_at(rest, span.start, reference, options)?, Some(p)))
+    }
+
+    else
+    {
+        Ok((parse_rgba_at(s, span.start, reference, options)?, None))
+    }
+}
+
+// Splits a function into its name, its arguments
+// and the optional alpha argument.
+// Arguments are separated by commas or whitespace,
+// the alpha comes fourth with commas or after a slash.
+#[allow(clippy::suspicious_else_formatting)]
+fn split_function<'a>(token: &'a str, span: Range<usize>)
+    -> Result<(&'a str, Vec<Arg<'a>>, Option<Arg<'a>>), ColorError>
+{
+    let open = token.find('(').unwrap();
+
+    if !token.ends_with(')')
+    {
+        return Err(syntax_error("expected a closing parenthesis", span.end..span.end))
+    }
+
+    let name = &token[..open];
+    let inner = &token[open + 1..token.len() - 1];
+    let offset = span.start + open + 1;
+
+    if inner.contains('(') || inner.contains(')')
+    {
+        return Err(syntax_error("unexpected parenthesis", offset..offset + inner.len()))
+    }
+
+    let mut args: Vec<Arg> = Vec::new();
+    let mut alpha = None;
+
+    if inner.contains(',')
+    {
+        if let Some(i) = inner.find('/')
+        {
+            return Err(syntax_error("unexpected slash", offset + i..offset + i + 1))
+        }
+
+        for part in inner.split(',')
+        {
+            let arg = trim_span(part, sub_offset(inner, part, offset));
+            if arg.0.is_empty() {return Err(syntax_error("expected a value", arg.1))}
+            args.push(arg);
+        }
+
+        if args.len() == 4 {alpha = args.pop()}
+    }
+
+    else
+    {
+        let mut parts = inner.split('/');
+        let values = parts.next().unwrap();
+
+        for word in values.split_whitespace()
+        {
+            let start = sub_offset(inner, word, offset);
+            args.push((word, start..start + word.len()));
+        }
+
+        if let Some(part) = parts.next()
+        {
+            let arg = trim_span(part, sub_offset(inner, part, offset));
+            if arg.0.is_empty() || arg.0.contains(char::is_whitespace)
+            {
+                return Err(syntax_error("expected an alpha value", arg.1))
+            }
+
+            alpha = Some(arg);
+        }
+
+        if let Some(part) = parts.next()
+        {
+            let start = sub_offset(inner, part, offset) - 1;
+            return Err(syntax_error("unexpected slash", start..start + 1))
+        }
+    }
+
+    Ok((name, args, alpha))
+}
+
+// Checks the number of function arguments
+fn check_count(args: &[Arg], expected: usize, span: &Range<usize>) -> Result<(), ColorError>
+{
+    if args.len() == expected {return Ok(())}
+    Err(ColorError::ComponentCount {expected, found: args.len(), span: span.clone()})
+}
+
+// Parses a CSS number or percentage.
+// Returns the number and whether it was a percentage.
+// The "none" keyword is read as 0.
+pub(crate) fn parse_number(arg: &str, span: Range<usize>) -> Result<(f64, bool), ColorError>
+{
+    if arg.eq_ignore_ascii_case("none") {return Ok((0.0, false))}
+    let (digits, percent) = match arg.strip_suffix('%') {Some(d) => (d, true), None => (arg, false)};
+
+    let valid = !digits.is_empty() && digits.chars()
+        .all(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+
+    match digits.parse::<f64>()
+    {
+        Ok(n) if valid => Ok((n, percent)),
+        _ => Err(syntax_error("expected a number", span))
+    }
+}
+
+// Parses a percentage or a plain number
+// from 0 to 100 and returns it from 0 to 1
+pub(crate) fn parse_percentage(arg: &str, span: Range<usize>) -> Result<f64, ColorError>
+{
+    let (n, _) = parse_number(arg, span.clone())?;
+    if !(0.0..=100.0).contains(&n) {return Err(out_of_range(arg, span))}
+    Ok(n / 100.0)
+}
+
+// Parses an alpha value from 0 to 1 or 0% to 100%
+pub(crate) fn parse_alpha(arg: &str, span: Range<usize>) -> Result<f64, ColorError>
+{
+    let (n, percent) = parse_number(arg, span.clone())?;
+    let n = if percent {n / 100.0} else {n};
+    if !(0.0..=1.0).contains(&n) {return Err(out_of_range(arg, span))}
+    Ok(n)
+}
+
+// Parses a hue with an optional unit
+// and returns it in degrees
+pub(crate) fn parse_hue(arg: &str, span: Range<usize>) -> Result<f64, ColorError>
+{
+    let lower = arg.to_lowercase();
+
+    let (digits, factor) = if let Some(d) = lower.strip_suffix("deg") {(d, 1.0)}
+        else if let Some(d) = lower.strip_suffix("grad") {(d, 0.9)}
+        else if let Some(d) = lower.strip_suffix("rad") {(d, 180.0 / std::f64::consts::PI)}
+        else if let Some(d) = lower.strip_suffix("turn") {(d, 360.0)}
+        else {(&lower[..], 1.0)};
+
+    match parse_number(digits, span.clone())?
+    {
+        (_, true) => Err(syntax_error("expected a hue", span)),
+        (n, false) => Ok(n * factor)
+    }
+}
+
+// Gets the position of a sub slice in the input
+// offset is the position of the parent slice
+pub(crate) fn sub_offset(parent: &str, child: &str, offset: usize) -> usize
+{
+    offset + (child.as_ptr() as usize - parent.as_ptr() as usize)
+}
+
+// Parses a comma separated "r,g,b" triple
+// offset is the position of the token in the input
+fn parse_triple(token: &str, offset: usize) -> Result<(u8, u8, u8), ColorError>
+{
+    let mut parts = Vec::new();
+    let mut pos = offset;
+
+    for part in token.split(',')
+    {
+        parts.push(trim_span(part, pos));
+        pos += part.len() + 1;
+    }
+
+    if parts.len() != 3
+    {
+        return Err(ColorError::ComponentCount
+        {
+            expected: 3,
+            found: parts.len(),
+            span: offset..offset + token.len()
+        })
+    }
+
+    let mut v = [0u8; 3];
+
+    for (i, (part, span)) in parts.into_iter().enumerate()
+    {
+        v[i] = parse_component(part, span)?;
+    }
+
+    Ok((v[0], v[1], v[2]))
+}
+
+// Parses a single 0-255 integer component
+fn parse_component(part: &str, span: Range<usize>) -> Result<u8, ColorError>
+{
+    let digits = part.strip_prefix('-').unwrap_or(part);
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(syntax_error("expected a number", span))
+    }
+
+    if part.starts_with('-') {return Err(out_of_range(part, span))}
+    part.parse::<u8>().map_err(|_| out_of_range(part, span))
+}
+
+// Trims a slice and returns it with its span
+// offset is the position of the slice in the input
+pub(crate) fn trim_span(s: &str, offset: usize) -> (&str, Range<usize>)
+{
+    let start = offset + s.len() - s.trim_start().len();
+    let token = s.trim();
+    (token, start..start + token.len())
+}
+
+pub(crate) fn syntax_error(message: &str, span: Range<usize>) -> ColorError
+{
+    ColorError::Syntax {message: message.to_string(), span}
+}
+
+pub(crate) fn out_of_range(value: &str, span: Range<usize>) -> ColorError
+{
+    ColorError::OutOfRange {value: value.to_string(), span}
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn try_parse_test()
+    {
+        assert_eq!(try_parse_color("cadetblue", (0, 0, 0)), Ok((95, 158, 160)));
+        assert_eq!(try_parse_color(" Cadet Blue ", (0, 0, 0)), Ok((95, 158, 160)));
+        assert_eq!(try_parse_color("10, 20, 30", (0, 0, 0)), Ok((10, 20, 30)));
+        assert_eq!(try_parse_color("#1e90ff", (0, 0, 0)), Ok((30, 144, 255)));
+        assert_eq!(try_parse_color("abc", (0, 0, 0)), Ok((170, 187, 204)));
+        assert_eq!(try_parse_color("darker2", (95, 158, 160)), Ok((57, 95, 96)));
+        assert_eq!(try_parse_color("saturate3", (70, 130, 180)), Ok((33, 133, 218)));
+        assert_eq!(try_parse_color("Desaturate5", (70, 130, 180)), Ok((125, 125, 125)));
+    }
+
+    #[test]
+    fn function_test()
+    {
+        let c = (0, 0, 0);
+        assert_eq!(try_parse_color("rgb(255, 99, 71)", c), Ok((255, 99, 71)));
+        assert_eq!(try_parse_color("RGB(255,99,71)", c), Ok((255, 99, 71)));
+        assert_eq!(try_parse_color("rgba(255, 99, 71, 0.5)", c), Ok((255, 99, 71)));
+        assert_eq!(try_parse_color("rgb(255 99 71 / 50%)", c), Ok((255, 99, 71)));
+        assert_eq!(try_parse_color("rgb(100% 0% 50%)", c), Ok((255, 0, 128)));
+        assert_eq!(try_parse_color("rgb(none 10 20)", c), Ok((0, 10, 20)));
+        assert_eq!(try_parse_color("hsl(120, 100%, 25%)", c), Ok((0, 128, 0)));
+        assert_eq!(try_parse_color("hsl(120deg 100% 25%)", c), Ok((0, 128, 0)));
+        assert_eq!(try_parse_color("hsl(0.5turn 100% 50%)", c), Ok((0, 255, 255)));
+        assert_eq!(try_parse_color("hsl(200grad 100% 50%)", c), Ok((0, 255, 255)));
+        assert_eq!(try_parse_color("hsl(3.14159rad 100% 50%)", c), Ok((0, 255, 255)));
+        assert_eq!(try_parse_color("hsla(240, 100%, 50%, 0.3)", c), Ok((0, 0, 255)));
+        assert_eq!(try_parse_color("hsl(240 100 50/.3)", c), Ok((0, 0, 255)));
+        assert_eq!(try_parse_color("hwb(0 20% 20%)", c), Ok((204, 51, 51)));
+        assert_eq!(try_parse_color("hwb(90 60% 60%)", c), Ok((128, 128, 128)));
+    }
+
+    #[test]
+    fn alpha_test()
+    {
+        let c = (0, 0, 0, 255);
+        assert_eq!(try_parse_rgba("#1e90ff80", c), Ok((30, 144, 255, 128)));
+        assert_eq!(try_parse_rgba("#abc8", c), Ok((170, 187, 204, 136)));
+        assert_eq!(try_parse_rgba("#1e90ff", c), Ok((30, 144, 255, 255)));
+        assert_eq!(try_parse_rgba("rgba(255, 99, 71, 0.5)", c), Ok((255, 99, 71, 128)));
+        assert_eq!(try_parse_rgba("hsl(120deg 100% 25% / 20%)", c), Ok((0, 128, 0, 51)));
+        assert_eq!(try_parse_rgba("red", c), Ok((255, 0, 0, 255)));
+        assert_eq!(try_parse_rgba("transparent", c), Ok((0, 0, 0, 0)));
+        assert_eq!(try_parse_rgba("darker", (95, 158, 160, 40)), Ok((76, 126, 128, 40)));
+        assert_eq!(try_parse_rgba("desaturate", (70, 130, 180, 40)), Ok((82, 129, 168, 40)));
+        assert_eq!(try_parse_color("transparent", (1, 2, 3)), Ok((0, 0, 0)));
+        assert!(try_parse_rgba("rgba(1, 2, 3, 1.5)", c).is_err());
+    }
+
+    #[test]
+    fn color_mix_test()
+    {
+        let c = (0, 0, 0, 255);
+        assert_eq!(try_parse_rgba("color-mix(in srgb, red, blue)", c), Ok((128, 0, 128, 255)));
+        assert_eq!(try_parse_rgba("color-mix(in srgb, 25% red, blue)", c), Ok((64, 0, 191, 255)));
+        assert_eq!(try_parse_rgba("color-mix(in srgb, red, blue 75%)", c), Ok((64, 0, 191, 255)));
+        assert_eq!(try_parse_rgba("color-mix(in oklch, red 40%, blue)", c), Ok((161, 0, 213, 255)));
+        assert_eq!(try_parse_rgba("Color-Mix(in HSL longer hue, red, blue)", c), Ok((0, 255, 0, 255)));
+        assert_eq!(try_parse_rgba("color-mix(in srgb, red 20%, blue 20%)", c), Ok((128, 0, 128, 102)));
+
+        let nested = "color-mix(in oklab, rgb(255 0 0), color-mix(in srgb, blue, white))";
+        assert_eq!(try_parse_rgba(nested, c), Ok((199, 106, 156, 255)));
+
+        let e = |s: &str| try_parse_rgba(s, c).unwrap_err();
+        assert_eq!(e("color-mix(in srgb, red 150%, blue)"), ColorError::OutOfRange {value: "150%".to_string(), span: 23..27});
+        assert_eq!(e("color-mix(in xyz, red, blue)").span(), 10..16);
+        assert_eq!(e("color-mix(in srgb, redd, blue)"), ColorError::UnknownName {name: "redd".to_string(), span: 19..23});
+        assert_eq!(e("color-mix(in srgb, red)"), ColorError::ComponentCount {expected: 3, found: 2, span: 0..23});
+        assert_eq!(e("color-mix(in oklab longer hue, red, blue)").span(), 10..29);
+        assert_eq!(e("color-mix(in srgb, red 0%, blue 0%)").span(), 0..35);
+        assert_eq!(e("color-mix(in srgb, red, blue").span(), 28..28);
+    }
+
+    #[test]
+    fn expression_test()
+    {
+        let c = (0, 0, 0, 255);
+        assert_eq!(try_parse_rgba("#336699 | lighter 15% | alpha 0.5", c), Ok((83, 140, 198, 128)));
+        assert_eq!(try_parse_rgba("steelblue darker2 desaturate rotate(30)", c), Ok((48, 50, 100, 255)));
+        assert_eq!(try_parse_rgba("10, 20, 30 invert", c), Ok((245, 235, 225, 255)));
+        assert_eq!(try_parse_rgba("Cadet Blue darker", c), try_parse_rgba("darker", (95, 158, 160, 255)));
+        assert_eq!(try_parse_rgba("rgb(255 0 0)|rotate -30|grayscale", c), Ok((128, 128, 128, 255)));
+        assert_eq!(try_parse_rgba("red lighter(12.5%)", c), Ok((255, 64, 64, 255)));
+        assert_eq!(try_parse_rgba("red complement alpha 20%", c), Ok((0, 255, 255, 51)));
+        assert_eq!(try_parse_rgba("invert", (10, 20, 30, 40)), Ok((245, 235, 225, 40)));
+
+        // Nested colors can have modifiers too
+        let mix = "color-mix(in srgb, red darker, blue) complement";
+        assert_eq!(try_parse_rgba(mix, c), Ok((26, 128, 0, 255)));
+    }
+
+    #[test]
+    fn amount_test()
+    {
+        let c = (70, 130, 180);
+        let lighter = try_parse_color("lighter 20%", c);
+        assert_eq!(lighter, Ok((141, 179, 211)));
+        assert_eq!(try_parse_color("lighter:20", c), lighter);
+        assert_eq!(try_parse_color("lighter(20)", c), lighter);
+        assert_eq!(try_parse_color("lighter 20", c), lighter);
+        assert_eq!(try_parse_color("lighter2", c), lighter);
+        assert_eq!(try_parse_color("darker:37", c), Ok((17, 32, 44)));
+        assert_eq!(try_parse_color("darker 12.5%", c), Ok((52, 97, 134)));
+
+        // Every amount is a percentage, whatever the separator
+        let darker = try_parse_color("darker 1%", c);
+        assert_ne!(darker, Ok((0, 0, 0)));
+        for s in &["darker 1", "darker:1", "darker(1)", "darker 1.0", "darker(1.0)"]
+        {
+            assert_eq!(try_parse_color(s, c), darker);
+        }
+        assert_eq!(try_parse_color("darker 37", c), try_parse_color("darker:37", c));
+        assert_eq!(try_parse_color("darker:1.5", c), try_parse_color("darker 1.5%", c));
+        assert_eq!(try_parse_color("darker 0.5", c), try_parse_color("darker(0.5%)", c));
+        assert_eq!(try_parse_color("steelblue rotate:90", c), Ok((175, 70, 180)));
+
+        let e = |s: &str| try_parse_color(s, c).unwrap_err();
+        assert_eq!(e("darker:150"), ColorError::OutOfRange {value: "150".to_string(), span: 7..10});
+        assert_eq!(e("darker:").span(), 7..7);
+        assert_eq!(e("darker 100.5"), ColorError::OutOfRange {value: "100.5".to_string(), span: 7..12});
+        assert_eq!(e("darker3:10").span(), 8..10);
+        assert_eq!(e("darker6").span(), 0..7);
+        assert_eq!(e("darker0").span(), 0..7);
+
+        // Custom steps
+        let options = ParseOptions {step_size: 5.0, max_steps: 20, ..Default::default()};
+        assert_eq!(try_parse_color_with("lighter4", c, &options), lighter);
+        assert_eq!(try_parse_color_with("lighter", c, &options), try_parse_color("lighter 5%", c));
+        assert!(try_parse_color_with("darker20", c, &options).is_ok());
+        assert!(try_parse_color_with("darker21", c, &options).is_err());
+        assert_eq!(try_parse_color_with("saturate6", c, &options), try_parse_color("saturate3", c));
+    }
+
+    #[test]
+    fn expression_error_test()
+    {
+        let e = |s: &str| try_parse_color(s, (0, 0, 0)).unwrap_err();
+        let syntax = |message: &str, span: Range<usize>| ColorError::Syntax {message: message.to_string(), span};
+
+        assert_eq!(e("red darkr"), syntax("unknown modifier", 4..9));
+        assert_eq!(e("steel blue foo"), syntax("unknown modifier", 11..14));
+        assert_eq!(e("red 20%"), syntax("unexpected amount", 4..7));
+        assert_eq!(e("red |"), syntax("expected a modifier", 5..5));
+        assert_eq!(e("red | blue"), syntax("expected a modifier", 6..10));
+        assert_eq!(e("| darker"), syntax("expected a color", 0..1));
+        assert_eq!(e("red rotate"), syntax("expected an amount", 10..10));
+        assert_eq!(e("red rotate()"), syntax("expected an amount", 11..11));
+        assert_eq!(e("red grayscale 20%"), syntax("unexpected amount", 14..17));
+        assert_eq!(e("red darker2 15%"), syntax("unexpected amount", 12..15));
+        assert_eq!(e("red rotate(30"), syntax("expected a closing parenthesis", 13..13));
+        assert_eq!(e("red darker 150%"), ColorError::OutOfRange {value: "150%".to_string(), span: 11..15});
+        assert_eq!(e("red alpha(2)"), ColorError::OutOfRange {value: "2".to_string(), span: 10..11});
+        assert_eq!(e("redd darker"), ColorError::UnknownName {name: "redd".to_string(), span: 0..4});
+    }
+
+    #[test]
+    fn function_error_test()
+    {
+        let c = (0, 0, 0);
+
+        assert_eq!(try_parse_color("rgb(1 2)", c),
+            Err(ColorError::ComponentCount {expected: 3, found: 2, span: 0..8}));
+
+        assert_eq!(try_parse_color("rgb(1, 256, 3)", c),
+            Err(ColorError::OutOfRange {value: "256".to_string(), span: 7..10}));
+
+        assert_eq!(try_parse_color("rgb(1 2 3 / 2)", c),
+            Err(ColorError::OutOfRange {value: "2".to_string(), span: 12..13}));
+
+        assert_eq!(try_parse_color("hsl(10 110% 50%)", c).unwrap_err().span(), 7..11);
+        assert_eq!(try_parse_color("rgb(1 x 3)", c).unwrap_err().span(), 6..7);
+        assert_eq!(try_parse_color("rgb(1 2 3", c).unwrap_err().span(), 9..9);
+        assert_eq!(try_parse_color("rgb(1, 2 / 3)", c).unwrap_err().span(), 9..10);
+        assert_eq!(try_parse_color("rgb(1 2 3 / 1 / 1)", c).unwrap_err().span(), 14..15);
+        assert_eq!(try_parse_color("rgb(1,,3)", c).unwrap_err().span(), 6..6);
+        assert_eq!(try_parse_color("foo(1 2 3)", c).unwrap_err().span(), 0..3);
+        assert_eq!(try_parse_color("hsl(10% 50% 50%)", c).unwrap_err().span(), 4..7);
+    }
+
+    #[test]
+    fn try_parse_error_test()
+    {
+        assert_eq!(try_parse_color("300,12,x", (0, 0, 0)),
+            Err(ColorError::OutOfRange {value: "300".to_string(), span: 0..3}));
+
+        assert_eq!(try_parse_color("30,12,x", (0, 0, 0)),
+            Err(ColorError::Syntax {message: "expected a number".to_string(), span: 6..7}));
+
+        assert_eq!(try_parse_color("1, -2, 3", (0, 0, 0)),
+            Err(ColorError::OutOfRange {value: "-2".to_string(), span: 3..5}));
+
+        assert_eq!(try_parse_color(" 1,2", (0, 0, 0)),
+            Err(ColorError::ComponentCount {expected: 3, found: 2, span: 1..4}));
+
+        assert_eq!(try_parse_color("  cornflower", (0, 0, 0)),
+            Err(ColorError::UnknownName {name: "cornflower".to_string(), span: 2..12}));
+
+        assert_eq!(try_parse_color("#12345", (0, 0, 0)),
+            Err(ColorError::Syntax {message: "invalid hex value".to_string(), span: 0..6}));
+
+        assert_eq!(try_parse_color("red!", (0, 0, 0)).unwrap_err().span(), 0..4);
+        assert_eq!(try_parse_color("  ", (0, 0, 0)).unwrap_err().span(), 2..2);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::*;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct RGB
 {
     red: u8,
@@ -183,6 +184,36 @@ impl RGB
         self.set_from_tuple(make_color_lighter(self.get_tuple(), amount));
     }
 
+    /// Makes the RGB darker by an amount
+    /// using a lightness model.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, LightnessModel};
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.make_darker_with(20.0, LightnessModel::OKLCh);
+    /// ```
+    pub fn make_darker_with(&mut self, amount: f64, model: LightnessModel)
+    {
+        self.set_from_tuple(make_color_darker_with(self.get_tuple(), amount, model));
+    }
+
+    /// Makes the RGB lighter by an amount
+    /// using a lightness model.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, LightnessModel};
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.make_lighter_with(20.0, LightnessModel::OKLCh);
+    /// ```
+    pub fn make_lighter_with(&mut self, amount: f64, model: LightnessModel)
+    {
+        self.set_from_tuple(make_color_lighter_with(self.get_tuple(), amount, model));
+    }
+
     /// Randomizes the RGB values.
     /// 
     /// # Example
@@ -239,6 +270,280 @@ impl RGB
         get_color_lightness(self.get_tuple())
     }
 
+    /// Sets the HSL hue value in degrees
+    /// keeping the saturation and lightness.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.set_hue(120.0);
+    /// ```
+    pub fn set_hue(&mut self, hue: f64)
+    {
+        self.set_from_tuple(set_color_hue(self.get_tuple(), hue));
+    }
+
+    /// Sets the HSL saturation value
+    /// keeping the hue and lightness.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.set_saturation(80.0);
+    /// ```
+    pub fn set_saturation(&mut self, saturation: f64)
+    {
+        self.set_from_tuple(set_color_saturation(self.get_tuple(), saturation));
+    }
+
+    /// Sets the HSL lightness value
+    /// keeping the hue and saturation.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.set_lightness(60.0);
+    /// ```
+    pub fn set_lightness(&mut self, lightness: f64)
+    {
+        self.set_from_tuple(set_color_lightness(self.get_tuple(), lightness));
+    }
+
+    /// Rotates the HSL hue by some degrees.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.rotate_hue(-30.0);
+    /// ```
+    pub fn rotate_hue(&mut self, degrees: f64)
+    {
+        self.set_from_tuple(rotate_color_hue(self.get_tuple(), degrees));
+    }
+
+    /// Makes the RGB more saturated by an amount.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.saturate(20.0);
+    /// ```
+    pub fn saturate(&mut self, amount: f64)
+    {
+        self.set_from_tuple(saturate_color(self.get_tuple(), amount));
+    }
+
+    /// Makes the RGB less saturated by an amount.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.desaturate(20.0);
+    /// ```
+    pub fn desaturate(&mut self, amount: f64)
+    {
+        self.set_from_tuple(desaturate_color(self.get_tuple(), amount));
+    }
+
+    /// Turns the RGB into a gray
+    /// with the same HSL lightness.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.grayscale();
+    /// ```
+    pub fn grayscale(&mut self)
+    {
+        self.set_from_tuple(grayscale_color(self.get_tuple()));
+    }
+
+    /// Inverts the RGB values.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.invert();
+    /// ```
+    pub fn invert(&mut self)
+    {
+        self.set_from_tuple(invert_color(self.get_tuple()));
+    }
+
+    /// Turns the RGB into its complement,
+    /// which has the opposite HSL hue.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.complement();
+    /// ```
+    pub fn complement(&mut self)
+    {
+        self.set_from_tuple(complement_color(self.get_tuple()));
+    }
+
+    /// Returns the HSV values
+    /// 
+    /// See the rgb_to_hsv definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let c = RGB::new(34, 66, 94);
+    /// let (hue, saturation, value) = c.get_hsv();
+    /// ```
+    pub fn get_hsv(&self) -> (f64, f64, f64)
+    {
+        rgb_to_hsv(self.get_tuple())
+    }
+
+    /// Returns the HSV saturation value
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let c = RGB::new(34, 66, 94);
+    /// let saturation = c.get_hsv_saturation();
+    /// ```
+    pub fn get_hsv_saturation(&self) -> f64
+    {
+        get_color_hsv_saturation(self.get_tuple())
+    }
+
+    /// Returns the HSV value (brightness)
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let c = RGB::new(34, 66, 94);
+    /// let value = c.get_value();
+    /// ```
+    pub fn get_value(&self) -> f64
+    {
+        get_color_value(self.get_tuple())
+    }
+
+    /// Sets the RGB values from HSV values.
+    /// 
+    /// See the hsv_to_rgb definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.set_from_hsv((210.0, 50.0, 40.0));
+    /// ```
+    pub fn set_from_hsv(&mut self, hsv: (f64, f64, f64))
+    {
+        self.set_from_tuple(hsv_to_rgb(hsv));
+    }
+
+    /// Sets the HSV saturation value
+    /// keeping the hue and value.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.set_hsv_saturation(80.0);
+    /// ```
+    pub fn set_hsv_saturation(&mut self, saturation: f64)
+    {
+        let hsv = self.get_hsv();
+        self.set_from_hsv((hsv.0, saturation, hsv.2));
+    }
+
+    /// Sets the HSV value (brightness)
+    /// keeping the hue and saturation.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.set_value(80.0);
+    /// ```
+    pub fn set_value(&mut self, value: f64)
+    {
+        let hsv = self.get_hsv();
+        self.set_from_hsv((hsv.0, hsv.1, value));
+    }
+
+    /// Turns the RGB into a HSV string.
+    /// 
+    /// See the color_to_hsv_string definition 
+    /// to check what the output is.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let c = RGB::new(34, 66, 94);
+    /// let s = c.to_hsv_string();
+    /// ```
+    pub fn to_hsv_string(&self) -> String
+    {
+        color_to_hsv_string(self.get_tuple())
+    }
+
+    /// Returns the name of the closest
+    /// built-in color and its distance.
+    /// 
+    /// See the nearest_color_name definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let c = RGB::new(34, 66, 94);
+    /// let (name, distance) = c.nearest_name();
+    /// ```
+    pub fn nearest_name(&self) -> (&'static str, f64)
+    {
+        nearest_color_name(self.get_tuple())
+    }
+
+    /// Returns the name of the built-in color
+    /// with exactly the same values, if any.
+    /// 
+    /// See the exact_color_name definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let c = RGB::new(255, 99, 71);
+    /// let name = c.exact_name();
+    /// ```
+    pub fn exact_name(&self) -> Option<&'static str>
+    {
+        exact_color_name(self.get_tuple())
+    }
+
     /// Turns the RGB into a string.
     /// 
     /// See the to_string definition 
@@ -251,6 +556,7 @@ impl RGB
     /// let c = RGB::new(34, 66, 94);
     /// let s = c.to_string();
     /// ```
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String
     {
         color_to_string(self.get_tuple())
@@ -307,6 +613,243 @@ impl RGB
         color_to_string_4(self.get_tuple(), prepend)
     }
 
+    /// Turns the RGB into a hex string.
+    /// 
+    /// See the color_to_hex definition 
+    /// to check what the output is.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let c = RGB::new(34, 66, 94);
+    /// let s = c.to_hex(false, true);
+    /// ```
+    pub fn to_hex(&self, uppercase: bool, short: bool) -> String
+    {
+        color_to_hex(self.get_tuple(), uppercase, short)
+    }
+
+    /// Blends a source color on top
+    /// of the RGB with a blend mode.
+    /// 
+    /// See the blend definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, BlendMode};
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.blend(RGB::new(128, 128, 128), BlendMode::Screen);
+    /// ```
+    pub fn blend(&mut self, source: RGB, mode: BlendMode)
+    {
+        self.set_from_tuple(blend(self.get_tuple(), source.get_tuple(), mode));
+    }
+
+    /// Mixes the RGB with another color.
+    /// 
+    /// See the mix definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, ColorSpace};
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.mix(RGB::new(255, 255, 255), 0.25, ColorSpace::OKLab);
+    /// ```
+    pub fn mix(&mut self, other: RGB, t: f64, space: ColorSpace)
+    {
+        self.set_from_tuple(mix(self.get_tuple(), other.get_tuple(), t, space));
+    }
+
+    /// Mixes the RGB with another color
+    /// with a hue interpolation method.
+    /// 
+    /// See the mix_with definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, ColorSpace, HueInterpolation};
+    /// let mut c = RGB::new(255, 0, 0);
+    /// c.mix_with(RGB::new(0, 0, 255), 0.5, ColorSpace::OKLCh, HueInterpolation::Longer);
+    /// ```
+    pub fn mix_with(&mut self, other: RGB, t: f64, space: ColorSpace, hue: HueInterpolation)
+    {
+        self.set_from_tuple(mix_with(self.get_tuple(), other.get_tuple(), t, space, hue));
+    }
+
+    /// Makes a color harmony with
+    /// the RGB as the base color.
+    /// 
+    /// See the color_harmony definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, Scheme};
+    /// let colors = RGB::new(34, 66, 94).harmony(Scheme::SplitComplementary);
+    /// ```
+    pub fn harmony(&self, scheme: Scheme) -> Vec<RGB>
+    {
+        color_harmony(self.get_tuple(), scheme).into_iter().map(RGB::from_tuple).collect()
+    }
+
+    /// Makes a color harmony with the RGB
+    /// as the base color using a lightness model.
+    /// 
+    /// See the color_harmony_with definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, Scheme, LightnessModel};
+    /// let colors = RGB::new(34, 66, 94).harmony_with(Scheme::Square, LightnessModel::OKLCh);
+    /// ```
+    pub fn harmony_with(&self, scheme: Scheme, model: LightnessModel) -> Vec<RGB>
+    {
+        color_harmony_with(self.get_tuple(), scheme, model).into_iter().map(RGB::from_tuple).collect()
+    }
+
+    /// Gets the WCAG 2 relative luminance
+    /// from 0 for black to 1 for white.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let luminance = RGB::new(34, 66, 94).get_relative_luminance();
+    /// ```
+    pub fn get_relative_luminance(&self) -> f64
+    {
+        relative_luminance(self.get_tuple())
+    }
+
+    /// Gets the WCAG 2 contrast ratio
+    /// with another RGB, from 1 to 21.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let ratio = RGB::new(34, 66, 94).contrast_ratio(&RGB::new(255, 255, 255));
+    /// ```
+    pub fn contrast_ratio(&self, other: &RGB) -> f64
+    {
+        contrast_ratio(self.get_tuple(), other.get_tuple())
+    }
+
+    /// Checks if the RGB has enough contrast
+    /// with another RGB to meet a WCAG 2 level.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, ContrastLevel};
+    /// let ok = RGB::new(34, 66, 94).meets_contrast(&RGB::new(255, 255, 255), ContrastLevel::AAA);
+    /// assert!(ok);
+    /// ```
+    pub fn meets_contrast(&self, other: &RGB, level: ContrastLevel) -> bool
+    {
+        meets_contrast(self.get_tuple(), other.get_tuple(), level)
+    }
+
+    /// Picks the candidate text color with the
+    /// highest WCAG 2 contrast on the RGB
+    /// as the background.
+    /// 
+    /// See the best_text_color definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let candidates = [RGB::new(255, 255, 255), RGB::new(0, 0, 0)];
+    /// let c = RGB::new(34, 66, 94).best_text_color(&candidates);
+    /// assert_eq!(c, Some(RGB::new(255, 255, 255)));
+    /// ```
+    pub fn best_text_color(&self, candidates: &[RGB]) -> Option<RGB>
+    {
+        let tuples: Vec<(u8, u8, u8)> = candidates.iter().map(|c| c.get_tuple()).collect();
+        best_text_color(self.get_tuple(), &tuples).map(RGB::from_tuple)
+    }
+
+    /// Makes the RGB darker or lighter until it has
+    /// at least a WCAG 2 contrast ratio on a background.
+    /// 
+    /// See the ensure_contrast definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let white = RGB::new(255, 255, 255);
+    /// let mut c = RGB::new(100, 149, 237);
+    /// c.ensure_contrast(&white, 4.5);
+    /// assert!(c.contrast_ratio(&white) >= 4.5);
+    /// ```
+    pub fn ensure_contrast(&mut self, background: &RGB, target: f64)
+    {
+        self.set_from_tuple(ensure_contrast(self.get_tuple(), background.get_tuple(), target));
+    }
+
+    /// Gets the APCA lightness contrast (Lc)
+    /// of the RGB as text on another RGB
+    /// as the background.
+    /// 
+    /// See the apca_contrast definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let lc = RGB::new(34, 66, 94).apca_contrast(&RGB::new(255, 255, 255));
+    /// ```
+    pub fn apca_contrast(&self, other: &RGB) -> f64
+    {
+        apca_contrast(self.get_tuple(), other.get_tuple())
+    }
+
+    /// Gets the difference with another RGB
+    /// using a Delta E formula.
+    /// 
+    /// See the Metric definition.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, Metric};
+    /// let d = RGB::new(34, 66, 94).delta_e(&RGB::new(36, 64, 96), Metric::CIEDE2000);
+    /// assert!(d < 3.0);
+    /// ```
+    pub fn delta_e(&self, other: &RGB, metric: Metric) -> f64
+    {
+        delta_e_rgb(self.get_tuple(), other.get_tuple(), metric)
+    }
+
+    /// Replaces the RGB with the closest
+    /// color of a palette.
+    /// 
+    /// It stays the same if the palette is empty.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, Palette, Metric};
+    /// let p = Palette::from_colors(&[RGB::new(0, 0, 0), RGB::new(255, 255, 255)]);
+    /// let mut c = RGB::new(20, 30, 40);
+    /// c.snap(&p, Metric::CIEDE2000);
+    /// assert_eq!(c, RGB::new(0, 0, 0));
+    /// ```
+    pub fn snap(&mut self, palette: &Palette, metric: Metric)
+    {
+        if let Some((i, _)) = palette.nearest(self, metric)
+        {
+            *self = palette.get_color(i).unwrap();
+        }
+    }
+
     /// Uses the parse function to 
     /// change the values of the RGB.
     /// 
@@ -320,6 +863,7 @@ impl RGB
     /// let mut c = RGB::new(34, 66, 94);
     /// c.change("red");
     /// c.change("46,39,199");
+    /// c.change("#1e90ff");
     /// c.change("darker");
     /// c.change("lighter");
     /// c.change("random");
@@ -328,6 +872,47 @@ impl RGB
     {
         self.set_from_tuple(parse_color(s, self.get_tuple()));
     }
+
+    /// Uses the parse function with options
+    /// to change the values of the RGB.
+    /// 
+    /// Check the parse_color_with definition
+    /// to check how to use it.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::{RGB, ParseOptions, LightnessModel};
+    /// let mut c = RGB::new(34, 66, 94);
+    /// c.change_with("darker", &ParseOptions {lightness: LightnessModel::OKLCh, ..Default::default()});
+    /// ```
+    pub fn change_with(&mut self, s: &str, options: &ParseOptions)
+    {
+        self.set_from_tuple(parse_color_with(s, self.get_tuple(), options));
+    }
+
+    /// Uses the strict parse function to 
+    /// change the values of the RGB.
+    /// 
+    /// The values are left untouched
+    /// if the string can't be parsed.
+    /// 
+    /// Check the try_parse_color definition
+    /// to check how to use it.
+    /// 
+    /// # Example
+    /// 
+    /// ```
+    /// use colorskill::RGB;
+    /// let mut c = RGB::new(34, 66, 94);
+    /// assert!(c.try_change("red").is_ok());
+    /// assert!(c.try_change("300,0,0").is_err());
+    /// ```
+    pub fn try_change(&mut self, s: &str) -> Result<(), ColorError>
+    {
+        self.set_from_tuple(try_parse_color(s, self.get_tuple())?);
+        Ok(())
+    }
 }
 
 // Unit Tests
@@ -376,6 +961,12 @@ mod tests
         assert_eq!(c.to_string_3("RGB"), "RGB(55,129,90)");
         assert_eq!(c.to_string_4(""), "(55, 129, 90)");
         assert_eq!(c.to_string_4("RGB"), "RGB(55, 129, 90)");
+        assert_eq!(c.to_hex(false, false), "#37815a");
+        assert_eq!(c.to_hex(true, true), "#37815A");
+
+        c.change("#fff");
+        assert_eq!(c.get_tuple(), (255, 255, 255));
+        c.set_from_tuple((55, 129, 90));
 
         let mut c2 = RGB::from_tuple((100, 90, 89));
 
@@ -388,6 +979,83 @@ mod tests
         assert_eq!(c2.get_saturation(), 5.94);
         assert_eq!(c2.get_lightness(), 57.06);
 
+        assert!(c2.try_change("1,2").is_err());
+        assert_eq!(c2.get_tuple(), (152, 140, 139));
+        assert!(c2.try_change("1,2,3").is_ok());
+        assert_eq!(c2.get_tuple(), (1, 2, 3));
+
+        let mut c3 = RGB::new(51, 77, 102);
+        assert_eq!(c3.get_hsv_saturation(), 50.0);
+        assert_eq!(c3.get_value(), 40.0);
+        assert_eq!(c3.to_hsv_string(), "hsv(209.41, 50%, 40%)");
+        c3.set_value(80.0);
+        assert_eq!(c3.get_tuple(), (102, 154, 204));
+        c3.set_hsv_saturation(0.0);
+        assert_eq!(c3.get_tuple(), (204, 204, 204));
+        c3.set_from_hsv((0.0, 100.0, 100.0));
+        assert_eq!(c3.get_tuple(), (255, 0, 0));
+
+        let mut c4 = RGB::new(95, 158, 160);
+        c4.make_darker_with(20.0, LightnessModel::OKLCh);
+        assert_eq!(c4.get_tuple(), (33, 98, 101));
+        c4.make_lighter_with(20.0, LightnessModel::OKLCh);
+        assert_eq!(c4.get_tuple(), (95, 158, 160));
+        c4.change_with("lighter2", &ParseOptions {lightness: LightnessModel::OKLCh, ..Default::default()});
+        assert_eq!(c4.get_tuple(), (157, 222, 224));
+
+        assert_eq!(c2.exact_name(), None);
+        assert_eq!(c2.nearest_name().0, "black");
+        c2.change("steelblue");
+        assert_eq!(c2.exact_name(), Some("steelblue"));
+
+        let mut c5 = RGB::new(200, 100, 50);
+        c5.blend(RGB::new(128, 128, 128), BlendMode::Multiply);
+        assert_eq!(c5.get_tuple(), (100, 50, 25));
+        c5.mix(RGB::new(200, 150, 125), 0.5, ColorSpace::SRGB);
+        assert_eq!(c5.get_tuple(), (150, 100, 75));
+        c5.mix_with(RGB::new(150, 100, 75), 1.0, ColorSpace::OKLCh, HueInterpolation::Longer);
+        assert_eq!(c5.get_tuple(), (150, 100, 75));
+
+        let colors = RGB::new(255, 0, 0).harmony(Scheme::Complementary);
+        assert_eq!(colors, vec![RGB::new(255, 0, 0), RGB::new(0, 255, 255)]);
+        assert_eq!(RGB::new(255, 0, 0).harmony_with(Scheme::Triadic, LightnessModel::OKLCh).len(), 3);
+
+        let white = RGB::new(255, 255, 255);
+        assert_eq!(white.get_relative_luminance(), 1.0);
+        assert_eq!(RGB::new(0, 0, 0).contrast_ratio(&white), 21.0);
+        assert!(RGB::new(118, 118, 118).meets_contrast(&white, ContrastLevel::AA));
+        assert!(!RGB::new(118, 118, 118).meets_contrast(&white, ContrastLevel::AAA));
+        assert!(RGB::new(0, 0, 0).apca_contrast(&white) > 100.0);
+        assert!(white.apca_contrast(&RGB::new(0, 0, 0)) < -100.0);
+
+        let mut c7 = RGB::new(255, 165, 0);
+        assert_eq!(c7.best_text_color(&[white, RGB::new(0, 0, 0)]), Some(RGB::new(0, 0, 0)));
+        c7.ensure_contrast(&white, 3.0);
+        assert!(c7.contrast_ratio(&white) >= 3.0);
+        assert_eq!(c7.delta_e(&c7, Metric::CMC(2.0, 1.0)), 0.0);
+        assert!(white.delta_e(&RGB::new(0, 0, 0), Metric::CIE76) > 99.0);
+
+        let mut c8 = RGB::new(250, 240, 245);
+        c8.snap(&Palette::from_colors(&[RGB::new(0, 0, 0), white]), Metric::CIE76);
+        assert_eq!(c8, white);
+
+        let mut c6 = RGB::new(70, 130, 180);
+        c6.rotate_hue(90.0);
+        assert_eq!(c6.get_tuple(), (175, 70, 180));
+        c6.set_hue(0.0);
+        c6.saturate(100.0);
+        assert_eq!(c6.get_tuple(), (250, 0, 0));
+        c6.desaturate(50.0);
+        c6.set_lightness(50.0);
+        assert_eq!(c6.get_tuple(), (191, 64, 64));
+        c6.set_saturation(100.0);
+        c6.complement();
+        assert_eq!(c6.get_tuple(), (0, 255, 255));
+        c6.invert();
+        assert_eq!(c6.get_tuple(), (255, 0, 0));
+        c6.grayscale();
+        assert_eq!(c6.get_tuple(), (128, 128, 128));
+
         c2.randomize();
     }
 }
\ No newline at end of file
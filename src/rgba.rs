@@ -0,0 +1,456 @@
+use crate::*;
+
+/// An RGB color with an alpha channel.
+///
+/// The values are stored straight
+/// (not premultiplied) as u8 numbers.
+///
+/// An alpha of 0 is fully transparent
+/// and 255 is fully opaque.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RGBA
+{
+    red: u8,
+    green: u8,
+    blue: u8,
+    alpha: u8
+}
+
+impl RGBA
+{
+    /// Makes a new RGBA from four u8 values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let c = RGBA::new(34, 66, 94, 128);
+    /// ```
+    pub fn new(red: u8, green: u8, blue: u8, alpha: u8) -> RGBA
+    {
+        RGBA
+        {
+            red, green, blue, alpha
+        }
+    }
+
+    /// Makes a new RGBA from a u8 tuple.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let c = RGBA::from_tuple((22, 95, 83, 200));
+    /// ```
+    pub fn from_tuple(t: (u8, u8, u8, u8)) -> RGBA
+    {
+        RGBA::new(t.0, t.1, t.2, t.3)
+    }
+
+    /// Makes a new RGBA from an RGB
+    /// and an alpha from 0 to 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{RGB, RGBA};
+    /// let c = RGBA::from_rgb(RGB::new(22, 95, 83), 0.5);
+    /// ```
+    pub fn from_rgb(c: RGB, alpha: f64) -> RGBA
+    {
+        let t = c.get_tuple();
+        RGBA::new(t.0, t.1, t.2, alpha_to_u8(alpha))
+    }
+
+    /// Gets the red value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let red = RGBA::new(34, 66, 94, 128).get_red();
+    /// ```
+    pub fn get_red(&self) -> u8
+    {
+        self.red
+    }
+
+    /// Gets the green value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let green = RGBA::new(34, 66, 94, 128).get_green();
+    /// ```
+    pub fn get_green(&self) -> u8
+    {
+        self.green
+    }
+
+    /// Gets the blue value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let blue = RGBA::new(34, 66, 94, 128).get_blue();
+    /// ```
+    pub fn get_blue(&self) -> u8
+    {
+        self.blue
+    }
+
+    /// Gets the alpha value as a u8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let alpha = RGBA::new(34, 66, 94, 128).get_alpha();
+    /// ```
+    pub fn get_alpha(&self) -> u8
+    {
+        self.alpha
+    }
+
+    /// Gets the alpha value from 0 to 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let alpha = RGBA::new(34, 66, 94, 51).get_alpha_f64();
+    /// assert_eq!(alpha, 0.2);
+    /// ```
+    pub fn get_alpha_f64(&self) -> f64
+    {
+        f64::from(self.alpha) / 255.0
+    }
+
+    /// Sets the red value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let mut c = RGBA::new(34, 66, 94, 128);
+    /// c.set_red(80);
+    /// ```
+    pub fn set_red(&mut self, value: u8)
+    {
+        self.red = value;
+    }
+
+    /// Sets the green value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let mut c = RGBA::new(34, 66, 94, 128);
+    /// c.set_green(80);
+    /// ```
+    pub fn set_green(&mut self, value: u8)
+    {
+        self.green = value;
+    }
+
+    /// Sets the blue value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let mut c = RGBA::new(34, 66, 94, 128);
+    /// c.set_blue(80);
+    /// ```
+    pub fn set_blue(&mut self, value: u8)
+    {
+        self.blue = value;
+    }
+
+    /// Sets the alpha value as a u8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let mut c = RGBA::new(34, 66, 94, 128);
+    /// c.set_alpha(255);
+    /// ```
+    pub fn set_alpha(&mut self, value: u8)
+    {
+        self.alpha = value;
+    }
+
+    /// Sets the alpha value from 0 to 1.
+    ///
+    /// Values outside of the range are clamped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let mut c = RGBA::new(34, 66, 94, 128);
+    /// c.set_alpha_f64(0.25);
+    /// ```
+    pub fn set_alpha_f64(&mut self, value: f64)
+    {
+        self.alpha = alpha_to_u8(value);
+    }
+
+    /// Sets all the values from a tuple.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let mut c = RGBA::new(34, 66, 94, 128);
+    /// c.set_from_tuple((19, 23, 129, 255));
+    /// ```
+    pub fn set_from_tuple(&mut self, t: (u8, u8, u8, u8))
+    {
+        self.set_red(t.0); self.set_green(t.1); self.set_blue(t.2); self.set_alpha(t.3);
+    }
+
+    /// Gets the values in a tuple.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let t = RGBA::new(34, 66, 94, 128).get_tuple();
+    /// ```
+    pub fn get_tuple(&self) -> (u8, u8, u8, u8)
+    {
+        (self.red, self.green, self.blue, self.alpha)
+    }
+
+    /// Gets the color without the alpha.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let rgb = RGBA::new(34, 66, 94, 128).get_rgb();
+    /// ```
+    pub fn get_rgb(&self) -> RGB
+    {
+        RGB::new(self.red, self.green, self.blue)
+    }
+
+    /// Gets the premultiplied values from 0 to 1.
+    ///
+    /// The color channels are multiplied
+    /// by the alpha, which is kept as is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let (r, g, b, a) = RGBA::new(255, 0, 0, 51).get_premultiplied();
+    /// assert_eq!((r, a), (0.2, 0.2));
+    /// ```
+    pub fn get_premultiplied(&self) -> (f64, f64, f64, f64)
+    {
+        let a = self.get_alpha_f64();
+        let f = |n: u8| f64::from(n) / 255.0 * a;
+        (f(self.red), f(self.green), f(self.blue), a)
+    }
+
+    /// Makes a new RGBA from premultiplied values from 0 to 1.
+    ///
+    /// A fully transparent color becomes
+    /// transparent black.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let c = RGBA::from_premultiplied((0.2, 0.0, 0.0, 0.2));
+    /// assert_eq!(c.get_tuple(), (255, 0, 0, 51));
+    /// ```
+    pub fn from_premultiplied(t: (f64, f64, f64, f64)) -> RGBA
+    {
+        if t.3 <= 0.0 {return RGBA::new(0, 0, 0, 0)}
+        let (r, g, b) = unit_rgb_to_tuple((t.0 / t.3, t.1 / t.3, t.2 / t.3));
+        RGBA::new(r, g, b, alpha_to_u8(t.3))
+    }
+
+    /// Composites the RGBA over an opaque background.
+    ///
+    /// This is the color users see when
+    /// the RGBA is drawn on top of the background.
+    ///
+    /// See the composite_over definition.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{RGB, RGBA};
+    /// let c = RGBA::new(255, 0, 0, 128).over(RGB::new(255, 255, 255));
+    /// ```
+    pub fn over(&self, background: RGB) -> RGB
+    {
+        RGB::from_tuple(composite_over(self.get_tuple(), background.get_tuple()))
+    }
+
+    /// Composites the RGBA over another RGBA
+    /// with the Porter-Duff over operator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let c = RGBA::new(255, 0, 0, 128).over_rgba(RGBA::new(0, 0, 255, 128));
+    /// ```
+    pub fn over_rgba(&self, background: RGBA) -> RGBA
+    {
+        let s = self.get_premultiplied();
+        let b = background.get_premultiplied();
+        let f = |cs: f64, cb: f64| cs + cb * (1.0 - s.3);
+        RGBA::from_premultiplied((f(s.0, b.0), f(s.1, b.1), f(s.2, b.2), f(s.3, b.3)))
+    }
+
+    /// Blends a source RGBA on top
+    /// of the RGBA with a blend mode.
+    ///
+    /// See the blend_rgba definition.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{RGBA, BlendMode};
+    /// let mut c = RGBA::new(34, 66, 94, 255);
+    /// c.blend(RGBA::new(255, 0, 0, 128), BlendMode::Multiply);
+    /// ```
+    pub fn blend(&mut self, source: RGBA, mode: BlendMode)
+    {
+        self.set_from_tuple(blend_rgba(self.get_tuple(), source.get_tuple(), mode));
+    }
+
+    /// Turns the RGBA into a hex string.
+    ///
+    /// See the color_to_hex_alpha definition
+    /// to check what the output is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let s = RGBA::new(34, 66, 94, 128).to_hex(false, false);
+    /// ```
+    pub fn to_hex(&self, uppercase: bool, short: bool) -> String
+    {
+        color_to_hex_alpha(self.get_tuple(), uppercase, short)
+    }
+
+    /// Uses the parse function to
+    /// change the values of the RGBA.
+    ///
+    /// Check the parse_rgba definition
+    /// to check how to use it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let mut c = RGBA::new(34, 66, 94, 255);
+    /// c.change("#1e90ff80");
+    /// c.change("rgba(30, 144, 255, 0.5)");
+    /// ```
+    pub fn change(&mut self, s: &str)
+    {
+        self.set_from_tuple(parse_rgba(s, self.get_tuple()));
+    }
+
+    /// Uses the strict parse function to
+    /// change the values of the RGBA.
+    ///
+    /// The values are left untouched
+    /// if the string can't be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::RGBA;
+    /// let mut c = RGBA::new(34, 66, 94, 255);
+    /// assert!(c.try_change("#1e90ff80").is_ok());
+    /// ```
+    pub fn try_change(&mut self, s: &str) -> Result<(), ColorError>
+    {
+        self.set_from_tuple(try_parse_rgba(s, self.get_tuple())?);
+        Ok(())
+    }
+}
+
+impl From<RGB> for RGBA
+{
+    fn from(c: RGB) -> RGBA
+    {
+        let t = c.get_tuple();
+        RGBA::new(t.0, t.1, t.2, 255)
+    }
+}
+
+impl From<RGBA> for RGB
+{
+    fn from(c: RGBA) -> RGB
+    {
+        c.get_rgb()
+    }
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn rgba_test()
+    {
+        let mut c = RGBA::new(255, 0, 0, 128);
+        assert_eq!(c.get_alpha(), 128);
+        assert_eq!(round_float(c.get_alpha_f64()), 0.5);
+
+        c.set_alpha_f64(0.2);
+        assert_eq!(c.get_tuple(), (255, 0, 0, 51));
+        assert_eq!(c.get_premultiplied(), (0.2, 0.0, 0.0, 0.2));
+        assert_eq!(RGBA::from_premultiplied(c.get_premultiplied()), c);
+        assert_eq!(RGBA::from_premultiplied((0.5, 0.5, 0.5, 0.0)), RGBA::new(0, 0, 0, 0));
+
+        assert_eq!(RGBA::from(RGB::new(1, 2, 3)), RGBA::new(1, 2, 3, 255));
+        assert_eq!(RGB::from(c), RGB::new(255, 0, 0));
+        assert_eq!(RGBA::from_rgb(RGB::new(1, 2, 3), 0.5), RGBA::new(1, 2, 3, 128));
+
+        c.change("#1e90ff80");
+        assert_eq!(c.get_tuple(), (30, 144, 255, 128));
+        assert_eq!(c.to_hex(false, false), "#1e90ff80");
+        assert!(c.try_change("rgba(1, 2, 3, 2)").is_err());
+        assert_eq!(c.get_tuple(), (30, 144, 255, 128));
+        c.change("invalid");
+        assert_eq!(c.get_tuple(), (30, 144, 255, 128));
+    }
+
+    #[test]
+    fn over_test()
+    {
+        let white = RGB::new(255, 255, 255);
+        assert_eq!(RGBA::new(255, 0, 0, 128).over(white), RGB::new(255, 127, 127));
+        assert_eq!(RGBA::new(255, 0, 0, 255).over(white), RGB::new(255, 0, 0));
+        assert_eq!(RGBA::new(255, 0, 0, 0).over(white), white);
+
+        let c = RGBA::new(255, 0, 0, 128).over_rgba(RGBA::new(0, 0, 255, 128));
+        assert_eq!(c, RGBA::new(170, 0, 85, 192));
+        assert_eq!(c.over(white), RGBA::new(255, 0, 0, 128).over(RGBA::new(0, 0, 255, 128).over(white)));
+        assert_eq!(RGBA::new(9, 9, 9, 0).over_rgba(RGBA::new(0, 0, 0, 0)), RGBA::new(0, 0, 0, 0));
+
+        // Normal blending is the same as over
+        let mut b = RGBA::new(0, 0, 255, 128);
+        b.blend(RGBA::new(255, 0, 0, 128), BlendMode::Normal);
+        assert_eq!(b, c);
+    }
+}
@@ -0,0 +1,344 @@
+use crate::*;
+
+/// A transform applied to the values
+/// and the domain of a scale.
+///
+/// Log spreads values that grow exponentially
+/// and needs a domain above 0.
+///
+/// Sqrt is a gentler version of Log
+/// that works with 0 and negative values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ScaleTransform
+{
+    #[default]
+    Linear,
+    Log,
+    Sqrt
+}
+
+impl ScaleTransform
+{
+    fn apply(&self, value: f64) -> f64
+    {
+        match self
+        {
+            ScaleTransform::Linear => value,
+            ScaleTransform::Log => value.ln(),
+            ScaleTransform::Sqrt => value.signum() * value.abs().sqrt()
+        }
+    }
+}
+
+// The way a scale picks colors
+#[derive(Clone, Debug, PartialEq)]
+enum ScaleKind
+{
+    Sequential(Gradient),
+    Diverging(f64, Gradient),
+    Quantize(Vec<RGB>),
+    Threshold(Vec<f64>, Vec<RGB>)
+}
+
+/// A scale that maps numbers to colors.
+///
+/// Sequential scales go from a minimum to a maximum,
+/// diverging scales have a midpoint in the middle
+/// of the gradient, quantize scales split the domain
+/// into equal buckets, and threshold scales use
+/// custom bucket limits.
+///
+/// Values outside of the domain are clamped.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::{Scale, Colormap};
+/// let scale = Scale::sequential(0.0, 100.0, Colormap::Viridis.to_gradient(32));
+/// let c = scale.get_color(42.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scale
+{
+    kind: ScaleKind,
+    min: f64,
+    max: f64,
+    transform: ScaleTransform
+}
+
+impl Scale
+{
+    /// Makes a scale that maps the domain
+    /// from min to max onto a gradient.
+    ///
+    /// The gradient is sampled from 0 to 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Scale, Gradient, ColorSpace, RGB};
+    /// let g = Gradient::from_colors(&[RGB::new(255, 255, 255), RGB::new(0, 0, 139)], ColorSpace::OKLab);
+    /// let scale = Scale::sequential(0.0, 10.0, g);
+    /// ```
+    pub fn sequential(min: f64, max: f64, gradient: Gradient) -> Scale
+    {
+        Scale
+        {
+            kind: ScaleKind::Sequential(gradient), min, max, transform: ScaleTransform::Linear
+        }
+    }
+
+    /// Makes a scale that maps min to the start
+    /// of a gradient, mid to its middle
+    /// and max to its end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Scale, Gradient, ColorSpace, RGB};
+    /// let colors = [RGB::new(0, 0, 255), RGB::new(255, 255, 255), RGB::new(255, 0, 0)];
+    /// let scale = Scale::diverging(-5.0, 0.0, 20.0, Gradient::from_colors(&colors, ColorSpace::OKLab));
+    /// assert_eq!(scale.get_color(0.0), RGB::new(255, 255, 255));
+    /// ```
+    pub fn diverging(min: f64, mid: f64, max: f64, gradient: Gradient) -> Scale
+    {
+        Scale
+        {
+            kind: ScaleKind::Diverging(mid, gradient), min, max, transform: ScaleTransform::Linear
+        }
+    }
+
+    /// Makes a scale that splits the domain
+    /// into as many equal buckets as colors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Scale, Colormap};
+    /// let scale = Scale::quantize(0.0, 1.0, &Colormap::Magma.take(5));
+    /// ```
+    pub fn quantize(min: f64, max: f64, colors: &[RGB]) -> Scale
+    {
+        Scale
+        {
+            kind: ScaleKind::Quantize(colors.to_vec()), min, max, transform: ScaleTransform::Linear
+        }
+    }
+
+    /// Makes a scale with custom bucket limits.
+    ///
+    /// Values below the first threshold get the
+    /// first color, values from the first threshold
+    /// to the second get the second color, and so on.
+    ///
+    /// There should be one more color than thresholds,
+    /// missing colors are replaced by the last one.
+    ///
+    /// The transform is not used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Scale, RGB};
+    /// let colors = [RGB::new(0, 128, 0), RGB::new(255, 165, 0), RGB::new(255, 0, 0)];
+    /// let scale = Scale::threshold(&[50.0, 90.0], &colors);
+    /// assert_eq!(scale.get_color(75.0), RGB::new(255, 165, 0));
+    /// ```
+    pub fn threshold(thresholds: &[f64], colors: &[RGB]) -> Scale
+    {
+        let mut thresholds = thresholds.to_vec();
+        thresholds.sort_by(f64::total_cmp);
+        let (min, max) = (thresholds.first().copied().unwrap_or(0.0), thresholds.last().copied().unwrap_or(0.0));
+
+        Scale
+        {
+            kind: ScaleKind::Threshold(thresholds, colors.to_vec()), min, max, transform: ScaleTransform::Linear
+        }
+    }
+
+    /// Gets the domain as a minimum and a maximum.
+    ///
+    /// For threshold scales these are
+    /// the first and last thresholds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Scale, Colormap};
+    /// let scale = Scale::quantize(0.0, 1.0, &Colormap::Magma.take(5));
+    /// assert_eq!(scale.get_domain(), (0.0, 1.0));
+    /// ```
+    pub fn get_domain(&self) -> (f64, f64)
+    {
+        (self.min, self.max)
+    }
+
+    /// Gets the transform.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Scale, Colormap};
+    /// let t = Scale::quantize(0.0, 1.0, &Colormap::Magma.take(5)).get_transform();
+    /// ```
+    pub fn get_transform(&self) -> ScaleTransform
+    {
+        self.transform
+    }
+
+    /// Sets the transform.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Scale, ScaleTransform, Colormap};
+    /// let mut scale = Scale::sequential(1.0, 1000.0, Colormap::Inferno.to_gradient(32));
+    /// scale.set_transform(ScaleTransform::Log);
+    /// ```
+    pub fn set_transform(&mut self, transform: ScaleTransform)
+    {
+        self.transform = transform;
+    }
+
+    /// Gets the color for a value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Scale, Colormap};
+    /// let scale = Scale::sequential(0.0, 100.0, Colormap::Viridis.to_gradient(32));
+    /// let c = scale.get_color(42.0);
+    /// ```
+    #[allow(clippy::suspicious_else_formatting)]
+    pub fn get_color(&self, value: f64) -> RGB
+    {
+        match &self.kind
+        {
+            ScaleKind::Sequential(gradient) => gradient.sample(self.normalize(value, self.min, self.max)),
+            ScaleKind::Diverging(mid, gradient) =>
+            {
+                let t = if value < *mid
+                {
+                    self.normalize(value, self.min, *mid) / 2.0
+                }
+
+                else
+                {
+                    0.5 + self.normalize(value, *mid, self.max) / 2.0
+                };
+
+                gradient.sample(t)
+            },
+            ScaleKind::Quantize(colors) =>
+            {
+                if colors.is_empty() {return RGB::new(0, 0, 0)}
+                let t = self.normalize(value, self.min, self.max);
+                let i = (t * colors.len() as f64).floor() as usize;
+                colors[i.min(colors.len() - 1)]
+            },
+            ScaleKind::Threshold(thresholds, colors) =>
+            {
+                if colors.is_empty() {return RGB::new(0, 0, 0)}
+                let i = thresholds.iter().take_while(|n| **n <= value).count();
+                colors[i.min(colors.len() - 1)]
+            }
+        }
+    }
+
+    /// Gets the colors for many values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{Scale, Colormap};
+    /// let scale = Scale::sequential(0.0, 100.0, Colormap::Viridis.to_gradient(32));
+    /// let colors = scale.get_colors(&[10.0, 20.0, 30.0]);
+    /// ```
+    pub fn get_colors(&self, values: &[f64]) -> Vec<RGB>
+    {
+        values.iter().map(|v| self.get_color(*v)).collect()
+    }
+
+    // Maps a value from a to b into 0 to 1
+    // going through the transform
+    fn normalize(&self, value: f64, a: f64, b: f64) -> f64
+    {
+        let f = |n: f64| self.transform.apply(n);
+        let range = f(b) - f(a);
+        if range == 0.0 {return 0.5}
+        let t = (f(value) - f(a)) / range;
+        if t.is_nan() {0.0} else {t.clamp(0.0, 1.0)}
+    }
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn gray() -> Gradient
+    {
+        Gradient::from_colors(&[RGB::new(0, 0, 0), RGB::new(255, 255, 255)], ColorSpace::SRGB)
+    }
+
+    #[test]
+    fn sequential_test()
+    {
+        let mut scale = Scale::sequential(0.0, 100.0, gray());
+        assert_eq!(scale.get_color(0.0), RGB::new(0, 0, 0));
+        assert_eq!(scale.get_color(50.0), RGB::new(128, 128, 128));
+        assert_eq!(scale.get_color(150.0), RGB::new(255, 255, 255));
+        assert_eq!(scale.get_color(-10.0), RGB::new(0, 0, 0));
+        assert_eq!(scale.get_colors(&[0.0, 100.0]), vec![RGB::new(0, 0, 0), RGB::new(255, 255, 255)]);
+
+        scale.set_transform(ScaleTransform::Sqrt);
+        assert_eq!(scale.get_color(25.0), RGB::new(128, 128, 128));
+
+        let mut scale = Scale::sequential(1.0, 10000.0, gray());
+        scale.set_transform(ScaleTransform::Log);
+        assert_eq!(scale.get_color(100.0), RGB::new(128, 128, 128));
+        assert_eq!(scale.get_color(0.0), RGB::new(0, 0, 0));
+
+        // A domain without a range maps to the middle
+        assert_eq!(Scale::sequential(5.0, 5.0, gray()).get_color(5.0), RGB::new(128, 128, 128));
+    }
+
+    #[test]
+    fn diverging_test()
+    {
+        let colors = [RGB::new(0, 0, 255), RGB::new(255, 255, 255), RGB::new(255, 0, 0)];
+        let scale = Scale::diverging(-10.0, 0.0, 100.0, Gradient::from_colors(&colors, ColorSpace::SRGB));
+        assert_eq!(scale.get_color(-10.0), colors[0]);
+        assert_eq!(scale.get_color(0.0), colors[1]);
+        assert_eq!(scale.get_color(100.0), colors[2]);
+
+        // Both sides are stretched to half of the gradient
+        assert_eq!(scale.get_color(-5.0), RGB::new(128, 128, 255));
+        assert_eq!(scale.get_color(50.0), RGB::new(255, 128, 128));
+    }
+
+    #[test]
+    fn bucket_test()
+    {
+        let colors = [RGB::new(1, 1, 1), RGB::new(2, 2, 2), RGB::new(3, 3, 3), RGB::new(4, 4, 4)];
+        let scale = Scale::quantize(0.0, 100.0, &colors);
+        assert_eq!(scale.get_color(0.0), colors[0]);
+        assert_eq!(scale.get_color(24.9), colors[0]);
+        assert_eq!(scale.get_color(25.0), colors[1]);
+        assert_eq!(scale.get_color(99.0), colors[3]);
+        assert_eq!(scale.get_color(100.0), colors[3]);
+        assert_eq!(Scale::quantize(0.0, 1.0, &[]).get_color(0.5), RGB::new(0, 0, 0));
+
+        let scale = Scale::threshold(&[90.0, 50.0], &colors[..3]);
+        assert_eq!(scale.get_domain(), (50.0, 90.0));
+        assert_eq!(scale.get_color(-1000.0), colors[0]);
+        assert_eq!(scale.get_color(50.0), colors[1]);
+        assert_eq!(scale.get_color(89.0), colors[1]);
+        assert_eq!(scale.get_color(90.0), colors[2]);
+
+        let scale = Scale::threshold(&[10.0, 20.0], &colors[..1]);
+        assert_eq!(scale.get_color(30.0), colors[0]);
+    }
+}
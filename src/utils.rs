@@ -1,4 +1,5 @@
 use rand::Rng;
+use crate::{RGB, Lab};
 
 // Lowercase and remove whitespace
 pub fn clean_string(s: &str) -> String
@@ -35,12 +36,171 @@ pub fn get_rgb_tuple_from_hsl(hsl: &colorsys::Hsl) -> (u8, u8, u8)
     )
 }
 
+// Converts HSL values into RGB values from 0 to 1.
+// The hue is in degrees and the saturation
+// and lightness go from 0 to 1.
+pub fn hsl_to_unit_rgb(h: f64, s: f64, l: f64) -> (f64, f64, f64)
+{
+    let f = |n: f64|
+    {
+        let k = (n + h / 30.0).rem_euclid(12.0);
+        let a = s * l.min(1.0 - l);
+        l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+
+    (f(0.0), f(8.0), f(4.0))
+}
+
+// Converts HSV values into RGB values from 0 to 1.
+// The hue is in degrees and the saturation
+// and value go from 0 to 1.
+pub fn hsv_to_unit_rgb(h: f64, s: f64, v: f64) -> (f64, f64, f64)
+{
+    let f = |n: f64|
+    {
+        let k = (n + h / 60.0).rem_euclid(6.0);
+        v - v * s * k.min(4.0 - k).clamp(0.0, 1.0)
+    };
+
+    (f(5.0), f(3.0), f(1.0))
+}
+
+// Converts HWB values into RGB values from 0 to 1.
+// The hue is in degrees and the whiteness
+// and blackness go from 0 to 1.
+pub fn hwb_to_unit_rgb(h: f64, w: f64, b: f64) -> (f64, f64, f64)
+{
+    if w + b >= 1.0
+    {
+        let gray = w / (w + b);
+        return (gray, gray, gray)
+    }
+
+    let rgb = hsl_to_unit_rgb(h, 1.0, 0.5);
+    let f = |c: f64| c * (1.0 - w - b) + w;
+    (f(rgb.0), f(rgb.1), f(rgb.2))
+}
+
+// Turns RGB values from 0 to 1 into a u8 tuple
+pub fn unit_rgb_to_tuple(t: (f64, f64, f64)) -> (u8, u8, u8)
+{
+    let f = |n: f64| (n * 255.0).round().clamp(0.0, 255.0) as u8;
+    (f(t.0), f(t.1), f(t.2))
+}
+
+// Converts an sRGB component from 0 to 1
+// into linear light, keeping the sign
+// of values outside of the range
+pub fn srgb_to_linear(c: f64) -> f64
+{
+    let abs = c.abs();
+    if abs <= 0.04045 {c / 12.92} else {c.signum() * ((abs + 0.055) / 1.055).powf(2.4)}
+}
+
+// Converts linear light into an sRGB
+// component, keeping the sign
+// of values outside of the range
+pub fn linear_to_srgb(c: f64) -> f64
+{
+    let abs = c.abs();
+    if abs <= 0.0031308 {c * 12.92} else {c.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)}
+}
+
+// Gets the CIE76 distance between two sRGB tuples
+pub fn lab_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64
+{
+    let (l1, a1, b1) = Lab::from(RGB::from_tuple(a)).get_tuple();
+    let (l2, a2, b2) = Lab::from(RGB::from_tuple(b)).get_tuple();
+    ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+}
+
+// Multiplies a 3x3 matrix by a vector
+pub fn multiply(m: &[[f64; 3]; 3], v: (f64, f64, f64)) -> (f64, f64, f64)
+{
+    (
+        m[0][0] * v.0 + m[0][1] * v.1 + m[0][2] * v.2,
+        m[1][0] * v.0 + m[1][1] * v.1 + m[1][2] * v.2,
+        m[2][0] * v.0 + m[2][1] * v.1 + m[2][2] * v.2
+    )
+}
+
+// Reads a comma separated RGB triple leniently,
+// invalid components become 0
+pub fn parse_lenient_triple(cs: &str) -> Option<(u8, u8, u8)>
+{
+    if !cs.contains(',') {return None}
+
+    let v: Vec<u8> = cs.split(',')
+        .map(|n| n.parse::<u8>().unwrap_or(0)).collect();
+
+    if v.len() != 3 {return None} Some((v[0], v[1], v[2]))
+}
+
+// Turns an alpha from 0 to 1 into a u8
+pub fn alpha_to_u8(a: f64) -> u8
+{
+    (a * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
 // Rounds a float to 2 decimal numbers
 pub fn round_float(n: f64) -> f64
 {
     (n * 100.0).round() / 100.0
 }
 
+// Parses a hex color string into an RGBA tuple.
+// Accepts 3, 4, 6 or 8 digits with an optional
+// "#" or "0x" prefix. Short forms are expanded
+// and a missing alpha is returned as 255.
+pub fn parse_hex(s: &str) -> Option<(u8, u8, u8, u8)>
+{
+    let digits = if let Some(d) = s.strip_prefix('#') {d}
+        else if let Some(d) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {d}
+        else {s};
+
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {return None}
+
+    let v: Vec<u8> = match digits.len()
+    {
+        3 | 4 => digits.chars()
+            .map(|c| c.to_digit(16).unwrap() as u8 * 17).collect(),
+        6 | 8 => (0..digits.len()).step_by(2)
+            .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap()).collect(),
+        _ => return None
+    };
+
+    Some((v[0], v[1], v[2], if v.len() == 4 {v[3]} else {255}))
+}
+
+// Counts the edits needed to turn a into b.
+// Insertions, deletions, substitutions and
+// swaps of adjacent characters cost 1 each.
+pub fn edit_distance(a: &str, b: &str) -> usize
+{
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {row[0] = i}
+    for (j, v) in d[0].iter_mut().enumerate() {*v = j}
+
+    for i in 1..=a.len()
+    {
+        for j in 1..=b.len()
+        {
+            let cost = if a[i - 1] == b[j - 1] {0} else {1};
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1]
+            {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
 // Gets a random u8 number
 pub fn random_u8() -> u8
 {
@@ -58,6 +218,25 @@ mod tests
     {
         assert_eq!(clean_string("A  momenT   Lapse"), "amomentlapse");
         assert_eq!(round_float(40.842135), 40.84);
+        assert_eq!(parse_hex("#1e90ff"), Some((30, 144, 255, 255)));
+        assert_eq!(parse_hex("0xABC"), Some((170, 187, 204, 255)));
+        assert_eq!(parse_hex("abc8"), Some((170, 187, 204, 136)));
+        assert_eq!(parse_hex("#1e90ff80"), Some((30, 144, 255, 128)));
+        assert_eq!(parse_hex("#1e90f"), None);
+        assert_eq!(parse_hex("#gggggg"), None);
+        assert_eq!(unit_rgb_to_tuple(hsl_to_unit_rgb(120.0, 1.0, 0.25)), (0, 128, 0));
+        assert_eq!(unit_rgb_to_tuple(hsl_to_unit_rgb(-120.0, 1.0, 0.5)), (0, 0, 255));
+        assert_eq!(unit_rgb_to_tuple(hsv_to_unit_rgb(210.0, 0.5, 0.4)), (51, 77, 102));
+        assert_eq!(unit_rgb_to_tuple(hwb_to_unit_rgb(0.0, 0.2, 0.2)), (204, 51, 51));
+        assert_eq!(unit_rgb_to_tuple(hwb_to_unit_rgb(0.0, 0.6, 0.6)), (128, 128, 128));
+        assert_eq!(linear_to_srgb(srgb_to_linear(0.5)), 0.5);
+        assert_eq!(srgb_to_linear(-1.0), -1.0);
+        assert_eq!(lab_distance((10, 20, 30), (10, 20, 30)), 0.0);
+        assert_eq!(alpha_to_u8(0.5), 128);
+        assert_eq!(alpha_to_u8(1.5), 255);
+        assert_eq!(edit_distance("bleu", "blue"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "red"), 3);
         random_u8();
     }
 }
\ No newline at end of file
@@ -0,0 +1,329 @@
+use crate::*;
+
+// sRGB to XYZ (D65) matrix from CSS Color 4
+const RGB_TO_XYZ: [[f64; 3]; 3] =
+[
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607]
+];
+
+// XYZ (D65) to sRGB matrix from CSS Color 4
+const XYZ_TO_RGB: [[f64; 3]; 3] =
+[
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [0.05563007969699366, -0.20397695888897652, 1.0569715142428786]
+];
+
+// Bradford chromatic adaptation from D65 to D50
+const D65_TO_D50: [[f64; 3]; 3] =
+[
+    [1.0479297925449969, 0.022946870601609652, -0.05019226628920524],
+    [0.02962780877005599, 0.9904344267538799, -0.017073799063418826],
+    [-0.009243040646204504, 0.015055191490298152, 0.7518742814281371]
+];
+
+// Bradford chromatic adaptation from D50 to D65
+const D50_TO_D65: [[f64; 3]; 3] =
+[
+    [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+    [-0.0283697093338637, 1.0099953980813041, 0.021041441191917323],
+    [0.012314014864481998, -0.020507649298898964, 1.330365926242124]
+];
+
+/// A CIE standard illuminant used
+/// as the reference white of a color.
+///
+/// D65 is the white of sRGB and the default.
+///
+/// D50 is the white used by ICC profiles
+/// and by the CSS lab() and lch() functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WhitePoint
+{
+    D50,
+    D65
+}
+
+impl WhitePoint
+{
+    /// Returns the XYZ values of the white point
+    /// with Y normalized to 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::WhitePoint;
+    /// let (x, y, z) = WhitePoint::D65.get_tuple();
+    /// ```
+    pub fn get_tuple(&self) -> (f64, f64, f64)
+    {
+        match self
+        {
+            WhitePoint::D50 => (0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585),
+            WhitePoint::D65 => (0.3127 / 0.3290, 1.0, (1.0 - 0.3127 - 0.3290) / 0.3290)
+        }
+    }
+}
+
+/// A color in the CIE 1931 XYZ color space.
+///
+/// Values are relative to a reference white
+/// with Y going from 0 to 1.
+///
+/// Conversions from RGB give D65 values,
+/// use to_white to adapt them to D50.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct XYZ
+{
+    x: f64,
+    y: f64,
+    z: f64,
+    white: WhitePoint
+}
+
+impl XYZ
+{
+    /// Makes a new D65 XYZ from three values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::XYZ;
+    /// let c = XYZ::new(0.41, 0.21, 0.02);
+    /// ```
+    pub fn new(x: f64, y: f64, z: f64) -> XYZ
+    {
+        XYZ::with_white(x, y, z, WhitePoint::D65)
+    }
+
+    /// Makes a new XYZ from three values
+    /// relative to a reference white.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{XYZ, WhitePoint};
+    /// let c = XYZ::with_white(0.43, 0.22, 0.01, WhitePoint::D50);
+    /// ```
+    pub fn with_white(x: f64, y: f64, z: f64, white: WhitePoint) -> XYZ
+    {
+        XYZ
+        {
+            x, y, z, white
+        }
+    }
+
+    /// Gets the X value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::XYZ;
+    /// let x = XYZ::new(0.41, 0.21, 0.02).get_x();
+    /// ```
+    pub fn get_x(&self) -> f64
+    {
+        self.x
+    }
+
+    /// Gets the Y value (luminance).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::XYZ;
+    /// let y = XYZ::new(0.41, 0.21, 0.02).get_y();
+    /// ```
+    pub fn get_y(&self) -> f64
+    {
+        self.y
+    }
+
+    /// Gets the Z value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::XYZ;
+    /// let z = XYZ::new(0.41, 0.21, 0.02).get_z();
+    /// ```
+    pub fn get_z(&self) -> f64
+    {
+        self.z
+    }
+
+    /// Gets the reference white.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::XYZ;
+    /// let white = XYZ::new(0.41, 0.21, 0.02).get_white();
+    /// ```
+    pub fn get_white(&self) -> WhitePoint
+    {
+        self.white
+    }
+
+    /// Gets the XYZ values in a tuple.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::XYZ;
+    /// let t = XYZ::new(0.41, 0.21, 0.02).get_tuple();
+    /// ```
+    pub fn get_tuple(&self) -> (f64, f64, f64)
+    {
+        (self.x, self.y, self.z)
+    }
+
+    /// Adapts the XYZ to another reference white
+    /// using the Bradford transform.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::{XYZ, WhitePoint};
+    /// let d50 = XYZ::new(0.41, 0.21, 0.02).to_white(WhitePoint::D50);
+    /// ```
+    pub fn to_white(&self, white: WhitePoint) -> XYZ
+    {
+        let m = match (self.white, white)
+        {
+            (WhitePoint::D65, WhitePoint::D50) => D65_TO_D50,
+            (WhitePoint::D50, WhitePoint::D65) => D50_TO_D65,
+            _ => return *self
+        };
+
+        let (x, y, z) = multiply(&m, self.get_tuple());
+        XYZ::with_white(x, y, z, white)
+    }
+
+    /// Makes a D65 XYZ from linear sRGB values.
+    ///
+    /// See the rgb_to_linear definition.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::XYZ;
+    /// let c = XYZ::from_linear_rgb((1.0, 0.0, 0.0));
+    /// ```
+    pub fn from_linear_rgb(t: (f64, f64, f64)) -> XYZ
+    {
+        let (x, y, z) = multiply(&RGB_TO_XYZ, t);
+        XYZ::new(x, y, z)
+    }
+
+    /// Returns the linear sRGB values.
+    ///
+    /// The values are not clamped so
+    /// colors outside of the sRGB gamut
+    /// go below 0 or above 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use colorskill::XYZ;
+    /// let t = XYZ::new(0.41, 0.21, 0.02).to_linear_rgb();
+    /// ```
+    pub fn to_linear_rgb(&self) -> (f64, f64, f64)
+    {
+        multiply(&XYZ_TO_RGB, self.to_white(WhitePoint::D65).get_tuple())
+    }
+}
+
+impl From<RGB> for XYZ
+{
+    fn from(c: RGB) -> XYZ
+    {
+        XYZ::from_linear_rgb(rgb_to_linear(c.get_tuple()))
+    }
+}
+
+impl From<XYZ> for RGB
+{
+    fn from(c: XYZ) -> RGB
+    {
+        RGB::from_tuple(linear_to_rgb(c.to_linear_rgb()))
+    }
+}
+
+/// Converts an sRGB tuple into
+/// linear light values from 0 to 1.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::rgb_to_linear;
+/// let lin = rgb_to_linear((30, 144, 255));
+/// ```
+pub fn rgb_to_linear(t: (u8, u8, u8)) -> (f64, f64, f64)
+{
+    let f = |n: u8| srgb_to_linear(f64::from(n) / 255.0);
+    (f(t.0), f(t.1), f(t.2))
+}
+
+/// Converts linear light values from 0 to 1
+/// into an sRGB tuple.
+///
+/// Values outside of the range are clamped.
+///
+/// # Example
+///
+/// ```
+/// use colorskill::linear_to_rgb;
+/// let c = linear_to_rgb((0.01, 0.28, 1.0));
+/// ```
+pub fn linear_to_rgb(t: (f64, f64, f64)) -> (u8, u8, u8)
+{
+    unit_rgb_to_tuple((linear_to_srgb(t.0), linear_to_srgb(t.1), linear_to_srgb(t.2)))
+}
+
+// Unit Tests
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn round4(t: (f64, f64, f64)) -> (f64, f64, f64)
+    {
+        let f = |n: f64| (n * 10000.0).round() / 10000.0;
+        (f(t.0), f(t.1), f(t.2))
+    }
+
+    #[test]
+    fn xyz_test()
+    {
+        let red = XYZ::from(RGB::new(255, 0, 0));
+        assert_eq!(round4(red.get_tuple()), (0.4124, 0.2126, 0.0193));
+        assert_eq!(red.get_white(), WhitePoint::D65);
+
+        let white = XYZ::from(RGB::new(255, 255, 255));
+        assert_eq!(round4(white.get_tuple()), round4(WhitePoint::D65.get_tuple()));
+
+        let white = white.to_white(WhitePoint::D50);
+        assert_eq!(round4(white.get_tuple()), round4(WhitePoint::D50.get_tuple()));
+        assert_eq!(round4(white.to_white(WhitePoint::D65).get_tuple()), round4(WhitePoint::D65.get_tuple()));
+
+        for t in [(255, 0, 0), (30, 144, 255), (12, 200, 99), (0, 0, 0)].iter()
+        {
+            let c = RGB::from_tuple(*t);
+            assert_eq!(RGB::from(XYZ::from(c)), c);
+            assert_eq!(RGB::from(XYZ::from(c).to_white(WhitePoint::D50)), c);
+        }
+    }
+
+    #[test]
+    fn linear_test()
+    {
+        assert_eq!(rgb_to_linear((0, 255, 0)), (0.0, 1.0, 0.0));
+        assert_eq!(round4(rgb_to_linear((128, 10, 188))), (0.2159, 0.003, 0.5029));
+        assert_eq!(linear_to_rgb((0.2159, 0.003, 0.5029)), (128, 10, 188));
+        assert_eq!(linear_to_rgb((-0.5, 1.5, 0.0)), (0, 255, 0));
+    }
+}